@@ -11,6 +11,8 @@
 pub mod internal;
 
 pub const ORT_API_VERSION: u32 = 16;
+/// The version of the `ort-sys` crate.
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub use std::ffi::{c_char, c_int, c_ulong, c_ulonglong, c_ushort, c_void};
 
@@ -1,10 +1,16 @@
-use std::{cell::UnsafeCell, ffi::CString, sync::atomic::AtomicPtr, sync::Arc};
+use std::{
+	cell::UnsafeCell,
+	ffi::CString,
+	sync::atomic::{AtomicPtr, Ordering},
+	sync::Arc
+};
 
 use tracing::debug;
 
 use super::{
 	custom_logger,
 	error::{Error, Result},
+	memory::{ArenaCfg, MemoryInfo},
 	ortsys, ExecutionProviderDispatch
 };
 #[cfg(feature = "load-dynamic")]
@@ -18,12 +24,36 @@ unsafe impl Sync for EnvironmentSingleton {}
 
 static G_ENV: EnvironmentSingleton = EnvironmentSingleton { cell: UnsafeCell::new(None) };
 
+/// The global ONNX Runtime environment, shared by all sessions. It is created by [`EnvironmentBuilder::commit`], or
+/// with default options when the first session is created.
 #[derive(Debug)]
-pub(crate) struct Environment {
+pub struct Environment {
 	pub(crate) execution_providers: Vec<ExecutionProviderDispatch>,
 	pub(crate) env_ptr: AtomicPtr<ort_sys::OrtEnv>
 }
 
+impl Environment {
+	/// Returns the global environment, creating it with default options if it hasn't been created yet.
+	pub fn current() -> Result<Arc<Environment>> {
+		get_environment().map(Arc::clone)
+	}
+
+	/// Creates an allocator for the device described by `memory_info` and registers it with the environment, so that
+	/// it can be shared by all sessions created with [`SessionBuilder::use_env_allocators`]. This lets several sessions
+	/// pool memory in one arena, instead of each creating their own.
+	///
+	/// `arena_cfg` configures the arena if `memory_info` uses [`AllocatorType::Arena`]; pass `None` to use the
+	/// defaults. Only one allocator can be registered per device; registering another returns an error.
+	///
+	/// [`SessionBuilder::use_env_allocators`]: crate::SessionBuilder::use_env_allocators
+	/// [`AllocatorType::Arena`]: crate::AllocatorType::Arena
+	pub fn create_and_register_allocator(&self, memory_info: &MemoryInfo, arena_cfg: Option<&ArenaCfg>) -> Result<()> {
+		let arena_cfg_ptr = arena_cfg.map_or(std::ptr::null(), |cfg| cfg.ptr as *const _);
+		ortsys![unsafe CreateAndRegisterAllocator(self.env_ptr.load(Ordering::Relaxed), memory_info.ptr, arena_cfg_ptr) -> Error::RegisterAllocator];
+		Ok(())
+	}
+}
+
 impl Drop for Environment {
 	#[tracing::instrument]
 	fn drop(&mut self) {
@@ -231,6 +261,52 @@ pub fn init_from(path: impl ToString) -> EnvironmentBuilder {
 	EnvironmentBuilder::default()
 }
 
+/// Loads the ONNX Runtime dynamic library at `path` and initializes the API function table, for when the library isn't
+/// on the default search path.
+///
+/// Unlike [`init_from`], which only records the path for the library to be loaded lazily (and panics then if it can't
+/// be), this loads the library immediately and returns an error if it can't be opened, isn't an ONNX Runtime build, or
+/// doesn't support the API version `ort` was built against. It must be called before any other `ort` API, since the
+/// library can only be loaded once; otherwise it returns [`Error::LibraryAlreadyLoaded`].
+///
+/// ```no_run
+/// # fn main() -> ort::Result<()> {
+/// ort::init_with_library("/opt/onnxruntime/lib/libonnxruntime.so")?.with_name("app").commit()?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "load-dynamic")]
+pub fn init_with_library(path: impl AsRef<std::path::Path>) -> Result<EnvironmentBuilder> {
+	let path = path.as_ref();
+	if crate::G_ORT_LIB.get().is_some() {
+		return Err(Error::LibraryAlreadyLoaded);
+	}
+
+	let load_error = |message: String| Error::LoadLibrary { path: path.to_path_buf(), message };
+	let lib = unsafe { libloading::Library::new(path) }.map_err(|e| load_error(e.to_string()))?;
+	let api = unsafe {
+		let base_getter: libloading::Symbol<unsafe extern "C" fn() -> *const ort_sys::OrtApiBase> =
+			lib.get(b"OrtGetApiBase").map_err(|e| load_error(e.to_string()))?;
+		let base = base_getter();
+		match base.as_ref().and_then(|base| base.GetApi) {
+			Some(get_api) => get_api(ort_sys::ORT_API_VERSION),
+			None => std::ptr::null()
+		}
+	};
+	if api.is_null() {
+		return Err(load_error(format!("library does not support ONNX Runtime API version {}", ort_sys::ORT_API_VERSION)));
+	}
+
+	let _ = G_ORT_DYLIB_PATH.set(Arc::new(path.display().to_string()));
+	crate::G_ORT_LIB
+		.set(Arc::new(std::sync::Mutex::new(lib)))
+		.map_err(|_| Error::LibraryAlreadyLoaded)?;
+	// store the function table checked above, so `crate::api()` doesn't resolve it again (and panic if it can't)
+	let _ = crate::G_ORT_API.set(Arc::new(std::sync::Mutex::new(AtomicPtr::new(api as *mut ort_sys::OrtApi))));
+
+	Ok(EnvironmentBuilder::default())
+}
+
 #[cfg(test)]
 mod tests {
 	use std::sync::{atomic::Ordering, Arc, OnceLock, RwLock, RwLockWriteGuard};
@@ -272,4 +348,16 @@ mod tests {
 		assert!(is_env_initialized());
 		assert_ne!(env_ptr(), None);
 	}
+
+	#[test]
+	#[cfg(feature = "load-dynamic")]
+	fn init_with_missing_library() {
+		match init_with_library("/nonexistent/libonnxruntime.so") {
+			Err(Error::LoadLibrary { path, .. }) => assert_eq!(path, std::path::Path::new("/nonexistent/libonnxruntime.so")),
+			// another test already loaded the real library
+			Err(Error::LibraryAlreadyLoaded) => {}
+			Err(e) => panic!("expected a load error, got {e:?}"),
+			Ok(_) => panic!("loaded a library that doesn't exist")
+		}
+	}
 }
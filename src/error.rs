@@ -4,7 +4,7 @@ use std::{convert::Infallible, io, path::PathBuf, string};
 
 use thiserror::Error;
 
-use super::{char_p_to_string, ortsys, tensor::TensorElementType, ValueType};
+use super::{ortsys, tensor::TensorElementType, ValueType};
 
 /// Type alias for the Result type returned by ORT functions.
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -24,6 +24,12 @@ pub enum Error {
 	/// An error occurred while creating an ONNX environment.
 	#[error("Failed to create ONNX Runtime environment: {0}")]
 	CreateEnvironment(ErrorInternal),
+	/// The ONNX Runtime dynamic library passed to `ort::init_with_library` could not be loaded.
+	#[error("Failed to load ONNX Runtime library at `{path}`: {message}")]
+	LoadLibrary { path: PathBuf, message: String },
+	/// `ort::init_with_library` was called after an ONNX Runtime library had already been loaded.
+	#[error("An ONNX Runtime library has already been loaded")]
+	LibraryAlreadyLoaded,
 	/// Error occurred when creating ONNX session options.
 	#[error("Failed to create ONNX Runtime session options: {0}")]
 	CreateSessionOptions(ErrorInternal),
@@ -45,6 +51,9 @@ pub enum Error {
 	/// Error occurred when getting ONNX type information
 	#[error("Failed to get onnx type from type info: {0}")]
 	GetOnnxTypeFromTypeInfo(ErrorInternal),
+	/// Error occurred when getting the ONNX type of a value
+	#[error("Failed to get value type: {0}")]
+	GetValueType(ErrorInternal),
 	/// Error occurred when casting ONNX type information to tensor information
 	#[error("Failed to cast type info to tensor info: {0}")]
 	CastTypeInfoToTensorInfo(ErrorInternal),
@@ -54,6 +63,12 @@ pub enum Error {
 	/// Error occurred when casting ONNX type information to map type info
 	#[error("Failed to cast type info to map typ info: {0}")]
 	CastTypeInfoToMapTypeInfo(ErrorInternal),
+	/// Error occurred when casting ONNX type information to optional type info
+	#[error("Failed to cast type info to optional type info: {0}")]
+	CastTypeInfoToOptionalTypeInfo(ErrorInternal),
+	/// Error occurred when getting the type contained by an optional type
+	#[error("Failed to get optional contained type info: {0}")]
+	GetOptionalContainedTypeInfo(ErrorInternal),
 	/// Error occurred when getting map key type
 	#[error("Failed to get map key type: {0}")]
 	GetMapKeyType(ErrorInternal),
@@ -105,6 +120,15 @@ pub enum Error {
 	/// Error occurred when extracting string data from an ONNX tensor
 	#[error("Failed to get tensor string data: {0}")]
 	GetStringTensorContent(ErrorInternal),
+	/// Error occurred when getting the length of a single element of a string tensor
+	#[error("Failed to get string tensor element length: {0}")]
+	GetStringTensorElementLength(ErrorInternal),
+	/// Error occurred when creating a memory arena configuration.
+	#[error("Failed to create arena config: {0}")]
+	CreateArenaCfg(ErrorInternal),
+	/// Error occurred when registering an allocator with the environment.
+	#[error("Failed to register allocator: {0}")]
+	RegisterAllocator(ErrorInternal),
 	/// Error occurred when creating run options.
 	#[error("Failed to create run options: {0}")]
 	CreateRunOptions(ErrorInternal),
@@ -114,6 +138,9 @@ pub enum Error {
 	/// Error occurred when unterminating run options.
 	#[error("Failed to unterminate run options: {0}")]
 	RunOptionsUnsetTerminate(ErrorInternal),
+	/// Error occurred when adding a config entry to run options.
+	#[error("Failed to add run config entry: {0}")]
+	AddRunConfigEntry(ErrorInternal),
 	/// Error occurred when converting data to a String
 	#[error("Data was not UTF-8: {0}")]
 	StringFromUtf8Error(#[from] string::FromUtf8Error),
@@ -171,14 +198,20 @@ pub enum Error {
 	ExecutionProvider(ErrorInternal),
 	#[error("Execution provider `{0}` was not registered because its corresponding Cargo feature is disabled.")]
 	ExecutionProviderNotRegistered(&'static str),
+	#[error("Execution provider `{0}` is not available in this build of ONNX Runtime.")]
+	ExecutionProviderNotAvailable(&'static str),
 	#[error("Expected tensor to be on CPU in order to get data, but had allocation device `{0}`.")]
 	TensorNotOnCpu(&'static str),
 	#[error("String tensors require the session's allocator to be provided through `Value::from_array`.")]
 	StringTensorRequiresAllocator,
 	#[error("Failed to create memory info: {0}")]
 	CreateMemoryInfo(ErrorInternal),
+	#[error("Failed to get tensor memory info: {0}")]
+	GetTensorMemoryInfo(ErrorInternal),
 	#[error("Could not get allocation device from `MemoryInfo`: {0}")]
 	GetAllocationDevice(ErrorInternal),
+	#[error("Failed to query `MemoryInfo`: {0}")]
+	GetMemoryInfo(ErrorInternal),
 	#[error("Failed to get available execution providers: {0}")]
 	GetAvailableProviders(ErrorInternal),
 	#[error("Unknown allocation device `{0}`")]
@@ -202,7 +235,110 @@ pub enum Error {
 	#[error("Error occurred while attempting to extract data from sequence value: {0}")]
 	ExtractSequence(ErrorInternal),
 	#[error("Error occurred while attempting to extract data from map value: {0}")]
-	ExtractMap(ErrorInternal)
+	ExtractMap(ErrorInternal),
+	#[error("Failed to create sparse tensor: {0}")]
+	CreateSparseTensor(ErrorInternal),
+	#[error("Failed to get sparse tensor format: {0}")]
+	GetSparseTensorFormat(ErrorInternal),
+	#[error("Failed to get sparse tensor values: {0}")]
+	GetSparseTensorValues(ErrorInternal),
+	#[error("Failed to get sparse tensor indices: {0}")]
+	GetSparseTensorIndices(ErrorInternal),
+	#[error("Value is not a sparse tensor")]
+	NotSparseTensor,
+	#[error("Expected a tensor with exactly one element, but it has {0} elements")]
+	NotScalar(usize),
+	#[error("Dimension index {index} is out of range for tensor of rank {rank}")]
+	DimensionOutOfRange { index: usize, rank: usize },
+	#[error("Got {names} input names but {values} input values")]
+	InputCountMismatch { names: usize, values: usize },
+	#[error("Input index {index} is out of range for {count} inputs")]
+	InputIndexOutOfRange { index: usize, count: usize },
+	#[error("Got {names} output names but {values} output values")]
+	OutputCountMismatch { names: usize, values: usize },
+	#[error("Tensor data is not properly aligned for the requested element type")]
+	MisalignedTensorData,
+	#[error("Bool tensor contains bytes other than 0 or 1")]
+	InvalidBoolTensorData,
+	#[error("Model has no input named `{0}`")]
+	UnknownInput(String),
+	#[error("Model has no output named `{0}`")]
+	UnknownOutput(String),
+	#[error("Output `{0}` was given more than once")]
+	DuplicateOutput(String),
+	#[error("Model input `{0}` is not a tensor")]
+	InputNotTensor(String),
+	#[error("Model output `{0}` is not a tensor")]
+	OutputNotTensor(String),
+	#[error("Shape {actual:?} does not match shape {expected:?} of model input `{name}`")]
+	InputShapeMismatch { name: String, expected: Vec<i64>, actual: Vec<i64> },
+	#[error("Model input `{0}` has symbolic dimensions, so a shape must be provided")]
+	MissingInputShape(String),
+	#[error("Shape {0:?} has a negative dimension or too many elements")]
+	InvalidShape(Vec<i64>),
+	#[error("Data of length {actual} is not densely packed for shape {shape:?}, which has {expected} elements")]
+	NotDenselyPacked { shape: Vec<i64>, expected: usize, actual: usize },
+	#[error("Input `{0}` is required by the model and cannot be omitted")]
+	RequiredInputOmitted(String),
+	#[error("Input `{name}` should have element type {expected:?}, but got {got:?}")]
+	InputTypeMismatch { name: String, expected: TensorElementType, got: TensorElementType },
+	#[error("Output `{name}` was allocated with element type {expected:?}, but has element type {got:?}")]
+	OutputTypeMismatch { name: String, expected: TensorElementType, got: TensorElementType },
+	#[error("Only 8-bit integer tensors can be dequantized, but got a tensor of {0:?}")]
+	NotQuantized(TensorElementType),
+	#[error("Failed to create initializer `{name}`: {source}")]
+	CreateInitializer { name: String, source: Box<crate::RunError> },
+	#[error("Dimensions {dimensions:?} of output `{name}` can't be determined from the input shapes alone")]
+	UnresolvedOutputShape { name: String, dimensions: Vec<i64> },
+	/// The operation isn't supported for tensors of this element type, e.g. getting the element size of a string
+	/// tensor.
+	#[error("Unsupported element type {0:?}")]
+	UnsupportedElementType(TensorElementType),
+	/// The model's protobuf couldn't be read while reading its [`ModelHeader`](crate::ModelHeader).
+	#[error("Failed to read model header: {0}")]
+	ReadModelHeader(#[source] io::Error),
+	/// ONNX Runtime logged warnings while loading a model with
+	/// [`SessionBuilder::strict_model_validation`](crate::SessionBuilder::strict_model_validation) enabled.
+	#[error("Model loaded with {} warning(s): {}", .0.len(), .0.join("; "))]
+	ModelValidation(Vec<String>),
+	/// Shape inference failed while loading the model, e.g. because a node's inputs have incompatible shapes.
+	#[error("Shape inference failed{}: {message}", node.as_ref().map(|node| format!(" for node `{node}`")).unwrap_or_default())]
+	ShapeInference {
+		/// The name of the node that failed shape inference, if ONNX Runtime reported it.
+		node: Option<String>,
+		/// ONNX Runtime's error message.
+		message: String
+	}
+}
+
+impl Error {
+	/// Maps an error from creating a session to [`Error::ShapeInference`] if it was caused by shape inference, or
+	/// [`Error::CreateSession`] otherwise.
+	pub(crate) fn create_session(error: ErrorInternal) -> Error {
+		match error {
+			ErrorInternal::Msg(message) if message.contains("ShapeInferenceError") => Error::ShapeInference {
+				node: shape_inference_error_node(&message).map(str::to_owned),
+				message
+			},
+			error => Error::CreateSession(error)
+		}
+	}
+}
+
+/// Extracts the node name from ONNX Runtime's shape inference error messages, which refer to the node as either
+/// `Node (Concat_42) Op (Concat) [ShapeInferenceError] ...` or `[ShapeInferenceError] (op_type:Concat, node name:
+/// Concat_42): ...`.
+fn shape_inference_error_node(message: &str) -> Option<&str> {
+	let (start, terminators): (usize, &[char]) = if let Some(i) = message.find("node name: ") {
+		(i + "node name: ".len(), &[')', ','])
+	} else if let Some(i) = message.find("Node (") {
+		(i + "Node (".len(), &[')'])
+	} else {
+		return None;
+	};
+	let rest = &message[start..];
+	let node = &rest[..rest.find(terminators)?];
+	(!node.is_empty()).then_some(node)
 }
 
 impl From<Infallible> for Error {
@@ -303,13 +439,10 @@ impl From<OrtStatusWrapper> for Result<(), ErrorInternal> {
 			Ok(())
 		} else {
 			let raw: *const std::os::raw::c_char = ortsys![unsafe GetErrorMessage(status.0)];
-			match char_p_to_string(raw) {
-				Ok(msg) => Err(ErrorInternal::Msg(msg)),
-				Err(err) => match err {
-					Error::FfiStringConversion(ErrorInternal::IntoStringError(e)) => Err(ErrorInternal::IntoStringError(e)),
-					_ => unreachable!()
-				}
-			}
+			// ONNX Runtime's messages are the only clue as to what went wrong (e.g. `Got invalid dimensions for input`), so
+			// keep them verbatim, replacing any invalid UTF-8 instead of discarding the whole message.
+			let msg = unsafe { std::ffi::CStr::from_ptr(raw) }.to_string_lossy().into_owned();
+			Err(ErrorInternal::Msg(msg))
 		}
 	}
 }
@@ -324,3 +457,52 @@ pub(crate) fn status_to_result(status: *mut ort_sys::OrtStatus) -> Result<(), Er
 	let status_wrapper: OrtStatusWrapper = status.into();
 	status_wrapper.into()
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_shape_inference_error() {
+		let error = Error::create_session(ErrorInternal::Msg(
+			"Load model from model.onnx failed:Node (Concat_42) Op (Concat) [ShapeInferenceError] All inputs to Concat must have same rank".to_owned()
+		));
+		assert!(matches!(error, Error::ShapeInference { node: Some(ref node), .. } if node == "Concat_42"));
+
+		let error = Error::create_session(ErrorInternal::Msg(
+			"[ShapeInferenceError] (op_type:Reshape, node name: /model/Reshape_1): Target shape may not have multiple -1 dimensions".to_owned()
+		));
+		assert!(matches!(error, Error::ShapeInference { node: Some(ref node), .. } if node == "/model/Reshape_1"));
+
+		let error = Error::create_session(ErrorInternal::Msg("[ShapeInferenceError] Inferred shape and existing shape differ in rank".to_owned()));
+		assert!(matches!(error, Error::ShapeInference { node: None, .. }));
+
+		let error = Error::create_session(ErrorInternal::Msg("Load model from model.onnx failed:Protobuf parsing failed.".to_owned()));
+		assert!(matches!(error, Error::CreateSession(_)));
+	}
+
+	#[test]
+	fn test_error_conversions() {
+		fn assert_send_sync<T: Send + Sync + 'static>() {}
+		assert_send_sync::<Error>();
+		assert_send_sync::<crate::RunError>();
+
+		fn run() -> std::result::Result<(), crate::RunError> {
+			Err(Error::UnknownOutput("y".to_owned()))?
+		}
+		fn with_anyhow() -> anyhow::Result<()> {
+			run()?;
+			Ok(())
+		}
+		fn with_boxed() -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+			run()?;
+			Ok(())
+		}
+
+		let error = with_anyhow().unwrap_err();
+		assert!(matches!(error.downcast_ref(), Some(crate::RunError::OrtError(Error::UnknownOutput(_)))));
+		let error = with_boxed().unwrap_err();
+		assert_eq!(error.to_string(), "Model has no output named `y`");
+		assert!(error.downcast_ref::<crate::RunError>().is_some());
+	}
+}
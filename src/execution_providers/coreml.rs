@@ -6,6 +6,12 @@ extern "C" {
 	pub(crate) fn OrtSessionOptionsAppendExecutionProvider_CoreML(options: *mut ort_sys::OrtSessionOptions, flags: u32) -> ort_sys::OrtStatusPtr;
 }
 
+/// The CoreML execution provider, for running models on the GPU and Apple Neural Engine (ANE) of macOS & iOS devices.
+///
+/// CoreML supports only a subset of ONNX operators (see the
+/// [list of supported operators](https://onnxruntime.ai/docs/execution-providers/CoreML-ExecutionProvider.html#supported-operators)).
+/// Unsupported nodes are assigned to the next execution provider, usually the CPU, so a model may end up partitioned
+/// between CoreML and the CPU. Verbose ONNX Runtime logs show how nodes were assigned.
 #[derive(Debug, Default, Clone)]
 pub struct CoreMLExecutionProvider {
 	use_cpu_only: bool,
@@ -56,15 +62,20 @@ impl ExecutionProvider for CoreMLExecutionProvider {
 		#[cfg(any(feature = "load-dynamic", feature = "coreml"))]
 		{
 			super::get_ep_register!(OrtSessionOptionsAppendExecutionProvider_CoreML(options: *mut ort_sys::OrtSessionOptions, flags: u32) -> ort_sys::OrtStatusPtr);
+			// https://github.com/microsoft/onnxruntime/blob/v1.16.3/include/onnxruntime/core/providers/coreml/coreml_provider_factory.h
+			const COREML_FLAG_USE_CPU_ONLY: u32 = 0x001;
+			const COREML_FLAG_ENABLE_ON_SUBGRAPH: u32 = 0x002;
+			const COREML_FLAG_ONLY_ENABLE_DEVICE_WITH_ANE: u32 = 0x004;
+
 			let mut flags = 0;
 			if self.use_cpu_only {
-				flags |= 0x001;
+				flags |= COREML_FLAG_USE_CPU_ONLY;
 			}
 			if self.enable_on_subgraph {
-				flags |= 0x002;
+				flags |= COREML_FLAG_ENABLE_ON_SUBGRAPH;
 			}
 			if self.only_enable_device_with_ane {
-				flags |= 0x004;
+				flags |= COREML_FLAG_ONLY_ENABLE_DEVICE_WITH_ANE;
 			}
 			return crate::error::status_to_result(unsafe { OrtSessionOptionsAppendExecutionProvider_CoreML(session_builder.session_options_ptr, flags) })
 				.map_err(Error::ExecutionProvider);
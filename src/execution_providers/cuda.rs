@@ -47,7 +47,8 @@ pub struct CUDAExecutionProvider {
 	cudnn_conv_use_max_workspace: Option<bool>,
 	cudnn_conv1d_pad_to_nc1d: Option<bool>,
 	enable_cuda_graph: Option<bool>,
-	enable_skip_layer_norm_strict_mode: Option<bool>
+	enable_skip_layer_norm_strict_mode: Option<bool>,
+	user_compute_stream: Option<usize>
 }
 
 impl CUDAExecutionProvider {
@@ -144,6 +145,22 @@ impl CUDAExecutionProvider {
 		self
 	}
 
+	/// Run the session's kernels on a user-managed CUDA stream (a `cudaStream_t`) instead of one created by ONNX
+	/// Runtime, e.g. to overlap inference with copies on other streams.
+	///
+	/// ONNX Runtime only submits work to the stream; it is up to the caller to synchronize it with any other streams
+	/// producing inputs or consuming outputs (e.g. with `cudaStreamWaitEvent`), and to call `cudaStreamSynchronize`
+	/// before reading outputs on the host.
+	///
+	/// # Safety
+	///
+	/// `stream` must be a valid CUDA stream on the configured device, and must outlive all sessions using this
+	/// execution provider.
+	pub unsafe fn with_compute_stream(mut self, stream: *mut std::ffi::c_void) -> Self {
+		self.user_compute_stream = Some(stream as usize);
+		self
+	}
+
 	pub fn build(self) -> ExecutionProviderDispatch {
 		self.into()
 	}
@@ -182,7 +199,9 @@ impl ExecutionProvider for CUDAExecutionProvider {
 				cudnn_conv_use_max_workspace = self.cudnn_conv_use_max_workspace.map(<bool as Into<i32>>::into),
 				cudnn_conv1d_pad_to_nc1d = self.cudnn_conv1d_pad_to_nc1d.map(<bool as Into<i32>>::into),
 				enable_cuda_graph = self.enable_cuda_graph.map(<bool as Into<i32>>::into),
-				enable_skip_layer_norm_strict_mode = self.enable_skip_layer_norm_strict_mode.map(<bool as Into<i32>>::into)
+				enable_skip_layer_norm_strict_mode = self.enable_skip_layer_norm_strict_mode.map(<bool as Into<i32>>::into),
+				has_user_compute_stream = self.user_compute_stream.map(|_| 1),
+				user_compute_stream = self.user_compute_stream
 			};
 			if let Err(e) =
 				crate::error::status_to_result(crate::ortsys![unsafe UpdateCUDAProviderOptions(cuda_options, key_ptrs.as_ptr(), value_ptrs.as_ptr(), len as _)])
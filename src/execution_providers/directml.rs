@@ -6,6 +6,12 @@ extern "C" {
 	fn OrtSessionOptionsAppendExecutionProvider_DML(options: *mut ort_sys::OrtSessionOptions, device_id: std::os::raw::c_int) -> ort_sys::OrtStatusPtr;
 }
 
+/// The DirectML execution provider, for GPU inference on Windows.
+///
+/// DirectML doesn't support memory pattern optimization or parallel execution; sessions using it must be configured
+/// with [`SessionBuilder::with_memory_pattern(false)`](SessionBuilder::with_memory_pattern) and
+/// [`SessionBuilder::with_parallel_execution(false)`](SessionBuilder::with_parallel_execution), which
+/// `SessionBuilder::with_directml` does automatically.
 #[derive(Debug, Default, Clone)]
 pub struct DirectMLExecutionProvider {
 	device_id: i32
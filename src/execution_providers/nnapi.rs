@@ -6,6 +6,14 @@ extern "C" {
 	pub(crate) fn OrtSessionOptionsAppendExecutionProvider_Nnapi(options: *mut ort_sys::OrtSessionOptions, flags: u32) -> ort_sys::OrtStatusPtr;
 }
 
+/// The NNAPI execution provider, for hardware-accelerated inference on Android; see `SessionBuilder::with_nnapi`.
+///
+/// NNAPI only supports a subset of operators, and the graph is partitioned so that unsupported nodes run on ONNX
+/// Runtime's CPU kernels instead, with a copy at every boundary. Partitioning falls back to the CPU more often with:
+/// - [`NNAPIExecutionProvider::with_disable_cpu`], since operators that only NNAPI's own CPU implementation supports
+///   are then left to ONNX Runtime;
+/// - [`NNAPIExecutionProvider::with_nchw`], which fewer NNAPI drivers support than the default NHWC layout;
+/// - models with dynamic input shapes, which NNAPI does not support at all.
 #[derive(Debug, Default, Clone)]
 pub struct NNAPIExecutionProvider {
 	use_fp16: bool,
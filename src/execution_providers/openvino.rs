@@ -34,8 +34,9 @@ impl Default for OpenVINOExecutionProvider {
 }
 
 impl OpenVINOExecutionProvider {
-	/// Overrides the accelerator hardware type and precision with these values at runtime. If this option is not
-	/// explicitly set, default hardware and precision specified during build time is used.
+	/// Overrides the accelerator hardware type and precision with these values at runtime, e.g. `CPU`, `GPU`, `NPU`, or
+	/// `GPU.0`. If this option is not explicitly set, default hardware and precision specified during build time is
+	/// used.
 	pub fn with_device_type(mut self, device_type: impl ToString) -> Self {
 		self.device_type = Some(device_type.to_string());
 		self
@@ -107,23 +108,19 @@ impl ExecutionProvider for OpenVINOExecutionProvider {
 	fn register(&self, session_builder: &SessionBuilder) -> Result<()> {
 		#[cfg(any(feature = "load-dynamic", feature = "openvino"))]
 		{
+			if !self.is_available()? {
+				return Err(Error::ExecutionProviderNotAvailable(self.as_str()));
+			}
+
+			// the strings must outlive `SessionOptionsAppendExecutionProvider_OpenVINO`
+			let device_type = self.device_type.as_deref().map(std::ffi::CString::new).transpose()?;
+			let device_id = self.device_id.as_deref().map(std::ffi::CString::new).transpose()?;
+			let cache_dir = self.cache_dir.as_deref().map(std::ffi::CString::new).transpose()?;
 			let openvino_options = ort_sys::OrtOpenVINOProviderOptions {
-				device_type: self
-					.device_type
-					.clone()
-					.map(|x| x.as_bytes().as_ptr() as *const std::ffi::c_char)
-					.unwrap_or_else(std::ptr::null),
-				device_id: self
-					.device_id
-					.clone()
-					.map(|x| x.as_bytes().as_ptr() as *const std::ffi::c_char)
-					.unwrap_or_else(std::ptr::null),
+				device_type: device_type.as_ref().map(|x| x.as_ptr()).unwrap_or_else(std::ptr::null),
+				device_id: device_id.as_ref().map(|x| x.as_ptr()).unwrap_or_else(std::ptr::null),
 				num_of_threads: self.num_threads,
-				cache_dir: self
-					.cache_dir
-					.clone()
-					.map(|x| x.as_bytes().as_ptr() as *const std::ffi::c_char)
-					.unwrap_or_else(std::ptr::null),
+				cache_dir: cache_dir.as_ref().map(|x| x.as_ptr()).unwrap_or_else(std::ptr::null),
 				context: self.context,
 				enable_opencl_throttling: self.enable_opencl_throttling.into(),
 				enable_dynamic_shapes: self.enable_dynamic_shapes.into(),
@@ -0,0 +1,65 @@
+use super::{
+	CANNExecutionProvider, CPUExecutionProvider, CUDAExecutionProvider, ExecutionProviderDispatch, ROCmExecutionProvider, TensorRTExecutionProvider
+};
+
+/// An ordered list of execution providers, each pinned to a device, for
+/// [`SessionBuilder::with_model_from_file_with_policy`](crate::SessionBuilder::with_model_from_file_with_policy).
+///
+/// Providers are tried in the order they were added until a session is created, so a policy can pin a session to one
+/// GPU of a multi-GPU machine, fall back to another GPU of the same vendor, and finally to the CPU:
+///
+/// ```no_run
+/// # use ort::{DevicePolicy, Session};
+/// # fn main() -> ort::Result<()> {
+/// let policy = DevicePolicy::new().tensorrt(3).cuda(3).cuda(2).cpu();
+/// let (session, execution_provider) = Session::builder()?.with_model_from_file_with_policy(&policy, "model.onnx")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct DevicePolicy {
+	execution_providers: Vec<ExecutionProviderDispatch>
+}
+
+impl DevicePolicy {
+	/// Creates an empty policy.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Tries the CUDA execution provider, with default options, on the GPU with the given device ID.
+	pub fn cuda(self, device_id: i32) -> Self {
+		self.then(CUDAExecutionProvider::default().with_device_id(device_id))
+	}
+
+	/// Tries the TensorRT execution provider, with default options, on the GPU with the given device ID.
+	pub fn tensorrt(self, device_id: i32) -> Self {
+		self.then(TensorRTExecutionProvider::default().with_device_id(device_id))
+	}
+
+	/// Tries the ROCm execution provider, with default options, on the GPU with the given device ID.
+	pub fn rocm(self, device_id: i32) -> Self {
+		self.then(ROCmExecutionProvider::default().with_device_id(device_id))
+	}
+
+	/// Tries the CANN execution provider, with default options, on the NPU with the given device ID.
+	pub fn cann(self, device_id: i32) -> Self {
+		self.then(CANNExecutionProvider::default().with_device_id(device_id))
+	}
+
+	/// Tries the CPU execution provider.
+	pub fn cpu(self) -> Self {
+		self.then(CPUExecutionProvider::default())
+	}
+
+	/// Tries an otherwise-configured execution provider, e.g. CUDA with a memory limit.
+	pub fn then(mut self, execution_provider: impl Into<ExecutionProviderDispatch>) -> Self {
+		self.execution_providers.push(execution_provider.into());
+		self
+	}
+
+	/// Returns the execution providers of this policy, in the order they will be tried.
+	pub fn execution_providers(&self) -> &[ExecutionProviderDispatch] {
+		&self.execution_providers
+	}
+}
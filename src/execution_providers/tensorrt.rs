@@ -39,6 +39,7 @@ impl TensorRTExecutionProvider {
 		self
 	}
 
+	/// Maximum workspace size in bytes TensorRT may use when building an engine.
 	pub fn with_max_workspace_size(mut self, max_size: usize) -> Self {
 		self.max_workspace_size = Some(max_size);
 		self
@@ -54,6 +55,8 @@ impl TensorRTExecutionProvider {
 		self
 	}
 
+	/// Enable FP16 precision for faster inference on GPUs with FP16 support. Note that changing this option invalidates
+	/// any cached engines.
 	pub fn with_fp16(mut self, enable: bool) -> Self {
 		self.fp16_enable = Some(enable);
 		self
@@ -84,11 +87,21 @@ impl TensorRTExecutionProvider {
 		self
 	}
 
+	/// Enable TensorRT engine caching.
+	///
+	/// Building a TensorRT engine can take several minutes for larger models. With caching enabled, the engine is
+	/// built once during the first session creation and serialized to the
+	/// [engine cache path](TensorRTExecutionProvider::with_engine_cache_path); later sessions will load it from disk.
+	///
+	/// Cached engines are specific to the model, TensorRT version, GPU model, and precision settings. Delete the cache
+	/// if any of these change.
 	pub fn with_engine_cache(mut self, enable: bool) -> Self {
 		self.engine_cache_enable = Some(enable);
 		self
 	}
 
+	/// Directory in which to store cached TensorRT engines. Only used when
+	/// [engine caching](TensorRTExecutionProvider::with_engine_cache) is enabled.
 	pub fn with_engine_cache_path(mut self, path: impl ToString) -> Self {
 		self.engine_cache_path = Some(path.to_string());
 		self
@@ -177,6 +190,82 @@ impl TensorRTExecutionProvider {
 	pub fn build(self) -> ExecutionProviderDispatch {
 		self.into()
 	}
+
+	/// Returns the TensorRT provider options ONNX Runtime resolves from this configuration, serialized as
+	/// `key=value;` pairs. Options that were not explicitly set are included with their default values, which makes this
+	/// useful for logging exactly which settings a session will use.
+	///
+	/// ```no_run
+	/// # use ort::TensorRTExecutionProvider;
+	/// # fn main() -> ort::Result<()> {
+	/// let trt = TensorRTExecutionProvider::default().with_fp16(true);
+	/// println!("{}", trt.options_as_string()?);
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[allow(unused, unreachable_code)]
+	pub fn options_as_string(&self) -> Result<String> {
+		#[cfg(any(feature = "load-dynamic", feature = "tensorrt"))]
+		return self.with_provider_options(|trt_options| {
+			let allocator = crate::Allocator::default();
+			let mut options_ptr: *mut std::ffi::c_char = std::ptr::null_mut();
+			crate::error::status_to_result(crate::ortsys![unsafe GetTensorRTProviderOptionsAsString(trt_options, allocator.ptr, &mut options_ptr)])
+				.map_err(Error::ExecutionProvider)?;
+			crate::error::assert_non_null_pointer(options_ptr, "TensorRTProviderOptionsString")?;
+			let options = crate::char_p_to_string(options_ptr);
+			crate::ortfree!(unsafe allocator.ptr, options_ptr);
+			options
+		});
+
+		Err(Error::ExecutionProviderNotRegistered(self.as_str()))
+	}
+
+	/// Creates ONNX Runtime TensorRT provider options from this configuration and passes them to `f`. The options are
+	/// released once `f` returns.
+	#[cfg(any(feature = "load-dynamic", feature = "tensorrt"))]
+	fn with_provider_options<T>(&self, f: impl FnOnce(*mut ort_sys::OrtTensorRTProviderOptionsV2) -> Result<T>) -> Result<T> {
+		let mut trt_options: *mut ort_sys::OrtTensorRTProviderOptionsV2 = std::ptr::null_mut();
+		crate::error::status_to_result(crate::ortsys![unsafe CreateTensorRTProviderOptions(&mut trt_options)]).map_err(Error::ExecutionProvider)?;
+		let (key_ptrs, value_ptrs, len, keys, values) = super::map_keys! {
+			device_id = self.device_id,
+			trt_max_workspace_size = self.max_workspace_size,
+			trt_max_partition_iterations = self.max_partition_iterations,
+			trt_min_subgraph_size = self.min_subgraph_size,
+			trt_fp16_enable = self.fp16_enable.map(<bool as Into<i32>>::into),
+			trt_int8_enable = self.int8_enable.map(<bool as Into<i32>>::into),
+			trt_int8_use_native_calibration_table = self.int8_use_native_calibration_table.map(<bool as Into<i32>>::into),
+			trt_int8_calibration_table_name = self.int8_calibration_table_name.clone(),
+			trt_dla_enable = self.dla_enable.map(<bool as Into<i32>>::into),
+			trt_dla_core = self.dla_core,
+			trt_engine_cache_enable = self.engine_cache_enable.map(<bool as Into<i32>>::into),
+			trt_engine_cache_path = self.engine_cache_path.clone(),
+			trt_dump_subgraphs = self.dump_subgraphs.map(<bool as Into<i32>>::into),
+			trt_force_sequential_engine_build = self.force_sequential_engine_build.map(<bool as Into<i32>>::into),
+			trt_context_memory_sharing_enable = self.enable_context_memory_sharing.map(<bool as Into<i32>>::into),
+			trt_layer_norm_fp32_fallback = self.layer_norm_fp32_fallback.map(<bool as Into<i32>>::into),
+			trt_timing_cache_enable = self.timing_cache_enable.map(<bool as Into<i32>>::into),
+			// https://github.com/microsoft/onnxruntime/pull/17956
+			trt_force_timing_cache = self.force_timing_cache.map(<bool as Into<i32>>::into),
+			trt_detailed_build_log = self.detailed_build_log.map(<bool as Into<i32>>::into),
+			trt_build_heuristics_enable = self.enable_build_heuristics.map(<bool as Into<i32>>::into),
+			trt_sparsity_enable = self.enable_sparsity.map(<bool as Into<i32>>::into),
+			trt_builder_optimization_level = self.builder_optimization_level,
+			trt_auxiliary_streams = self.auxiliary_streams,
+			trt_tactic_sources = self.tactic_sources.clone(),
+			trt_extra_plugin_lib_paths = self.extra_plugin_lib_paths.clone(),
+			trt_profile_min_shapes = self.profile_min_shapes.clone(),
+			trt_profile_max_shapes = self.profile_max_shapes.clone(),
+			trt_profile_opt_shapes = self.profile_opt_shapes.clone()
+		};
+		let result = crate::error::status_to_result(
+			crate::ortsys![unsafe UpdateTensorRTProviderOptions(trt_options, key_ptrs.as_ptr(), value_ptrs.as_ptr(), len as _)]
+		)
+		.map_err(Error::ExecutionProvider)
+		.and_then(|_| f(trt_options));
+		crate::ortsys![unsafe ReleaseTensorRTProviderOptions(trt_options)];
+		std::mem::drop((keys, values));
+		result
+	}
 }
 
 impl From<TensorRTExecutionProvider> for ExecutionProviderDispatch {
@@ -193,55 +282,10 @@ impl ExecutionProvider for TensorRTExecutionProvider {
 	#[allow(unused, unreachable_code)]
 	fn register(&self, session_builder: &SessionBuilder) -> Result<()> {
 		#[cfg(any(feature = "load-dynamic", feature = "tensorrt"))]
-		{
-			let mut trt_options: *mut ort_sys::OrtTensorRTProviderOptionsV2 = std::ptr::null_mut();
-			crate::error::status_to_result(crate::ortsys![unsafe CreateTensorRTProviderOptions(&mut trt_options)]).map_err(Error::ExecutionProvider)?;
-			let (key_ptrs, value_ptrs, len, keys, values) = super::map_keys! {
-				device_id = self.device_id,
-				trt_max_workspace_size = self.max_workspace_size,
-				trt_max_partition_iterations = self.max_partition_iterations,
-				trt_min_subgraph_size = self.min_subgraph_size,
-				trt_fp16_enable = self.fp16_enable.map(<bool as Into<i32>>::into),
-				trt_int8_enable = self.int8_enable.map(<bool as Into<i32>>::into),
-				trt_int8_use_native_calibration_table = self.int8_use_native_calibration_table.map(<bool as Into<i32>>::into),
-				trt_int8_calibration_table_name = self.int8_calibration_table_name.clone(),
-				trt_dla_enable = self.dla_enable.map(<bool as Into<i32>>::into),
-				trt_dla_core = self.dla_core,
-				trt_engine_cache_enable = self.engine_cache_enable.map(<bool as Into<i32>>::into),
-				trt_engine_cache_path = self.engine_cache_path.clone(),
-				trt_dump_subgraphs = self.dump_subgraphs.map(<bool as Into<i32>>::into),
-				trt_force_sequential_engine_build = self.force_sequential_engine_build.map(<bool as Into<i32>>::into),
-				trt_context_memory_sharing_enable = self.enable_context_memory_sharing.map(<bool as Into<i32>>::into),
-				trt_layer_norm_fp32_fallback = self.layer_norm_fp32_fallback.map(<bool as Into<i32>>::into),
-				trt_timing_cache_enable = self.timing_cache_enable.map(<bool as Into<i32>>::into),
-				// https://github.com/microsoft/onnxruntime/pull/17956
-				trt_force_timing_cache = self.force_timing_cache.map(<bool as Into<i32>>::into),
-				trt_detailed_build_log = self.detailed_build_log.map(<bool as Into<i32>>::into),
-				trt_build_heuristics_enable = self.enable_build_heuristics.map(<bool as Into<i32>>::into),
-				trt_sparsity_enable = self.enable_sparsity.map(<bool as Into<i32>>::into),
-				trt_builder_optimization_level = self.builder_optimization_level,
-				trt_auxiliary_streams = self.auxiliary_streams,
-				trt_tactic_sources = self.tactic_sources.clone(),
-				trt_extra_plugin_lib_paths = self.extra_plugin_lib_paths.clone(),
-				trt_profile_min_shapes = self.profile_min_shapes.clone(),
-				trt_profile_max_shapes = self.profile_max_shapes.clone(),
-				trt_profile_opt_shapes = self.profile_opt_shapes.clone()
-			};
-			if let Err(e) = crate::error::status_to_result(
-				crate::ortsys![unsafe UpdateTensorRTProviderOptions(trt_options, key_ptrs.as_ptr(), value_ptrs.as_ptr(), len as _)]
-			)
-			.map_err(Error::ExecutionProvider)
-			{
-				crate::ortsys![unsafe ReleaseTensorRTProviderOptions(trt_options)];
-				std::mem::drop((keys, values));
-				return Err(e);
-			}
-
-			let status = crate::ortsys![unsafe SessionOptionsAppendExecutionProvider_TensorRT_V2(session_builder.session_options_ptr, trt_options)];
-			crate::ortsys![unsafe ReleaseTensorRTProviderOptions(trt_options)];
-			std::mem::drop((keys, values));
-			return crate::error::status_to_result(status).map_err(Error::ExecutionProvider);
-		}
+		return self.with_provider_options(|trt_options| {
+			crate::error::status_to_result(crate::ortsys![unsafe SessionOptionsAppendExecutionProvider_TensorRT_V2(session_builder.session_options_ptr, trt_options)])
+				.map_err(Error::ExecutionProvider)
+		});
 
 		Err(Error::ExecutionProviderNotRegistered(self.as_str()))
 	}
@@ -1,11 +1,17 @@
-use std::{ffi::CString, fmt::Debug, ptr, sync::Arc};
+use std::{
+	ffi::{c_char, CString},
+	fmt::Debug,
+	ops::DerefMut,
+	ptr,
+	sync::Arc
+};
 
 use crate::{
 	memory::MemoryInfo,
-	ortsys,
+	ortfree, ortsys,
 	session::{output::SessionOutputs, RunOptions},
 	value::Value,
-	Error, Result, Session
+	Error, IntoTensorElementType, Result, RustOwnerValue, Session
 };
 
 /// Enables binding of session inputs and/or outputs to pre-allocated memory.
@@ -55,6 +61,25 @@ impl<'s> IoBinding<'s> {
 		Ok(())
 	}
 
+	/// Bind a session output to a pre-allocated [`RustOwnerValue`] buffer in CPU memory, e.g. one created with
+	/// [`RustOwnerValue::zeros`].
+	///
+	/// Like [`IoBinding::bind_output`], the buffer stays bound across runs: every run writes into it in place, so
+	/// repeated runs with new inputs reuse the same memory without rebinding it. The [`Value`]s returned by
+	/// [`IoBinding::run`] view that same memory.
+	pub fn bind_output_buffer<'o: 's, S, T, C>(&mut self, name: S, buffer: &'o mut RustOwnerValue<C>) -> Result<()>
+	where
+		S: AsRef<str>,
+		C: DerefMut<Target = [T]>,
+		T: IntoTensorElementType + Debug + Clone + 'static
+	{
+		let name = name.as_ref();
+		let cname = CString::new(name)?;
+		ortsys![unsafe BindOutput(self.ptr, cname.as_ptr(), buffer.ptr_mut()) -> Error::BindOutput];
+		self.output_names.push(name.to_string());
+		Ok(())
+	}
+
 	/// Bind a session output to a device which is specified by `mem_info`.
 	pub fn bind_output_to_device<S: AsRef<str>>(&mut self, name: S, mem_info: MemoryInfo) -> Result<()> {
 		let name = name.as_ref();
@@ -64,21 +89,91 @@ impl<'s> IoBinding<'s> {
 		Ok(())
 	}
 
-	pub fn run<'i: 's>(&'i self) -> Result<SessionOutputs<'s>> {
+	/// Unbinds all inputs, releasing the values bound with [`IoBinding::bind_input`].
+	pub fn clear_bound_inputs(&mut self) {
+		ortsys![unsafe ClearBoundInputs(self.ptr)];
+		self.input_values.clear();
+	}
+
+	/// Unbinds all outputs, so they can be bound again, e.g. to buffers of a different shape.
+	///
+	/// An output bound with [`IoBinding::bind_output`] stays bound across runs, and every run writes into the same
+	/// buffer, so in a streaming loop it only needs to be bound once; an output bound with
+	/// [`IoBinding::bind_output_to_device`] is likewise reused once the first run has allocated it. A bound buffer must
+	/// have exactly the shape the model produces, though: if the output's shape changes between runs (e.g. with the
+	/// input resolution), the run fails, and the output must be cleared and bound to a buffer of the new shape.
+	pub fn clear_bound_outputs(&mut self) {
+		ortsys![unsafe ClearBoundOutputs(self.ptr)];
+		self.output_names.clear();
+	}
+
+	/// Returns the names of the outputs bound to this binding, in the order they were bound.
+	pub fn bound_output_names(&self) -> Result<Vec<String>> {
+		let allocator = self.session.allocator();
+		let mut buffer: *mut c_char = ptr::null_mut();
+		let mut lengths: *mut ort_sys::size_t = ptr::null_mut();
+		let mut count: ort_sys::size_t = 0;
+		ortsys![unsafe GetBoundOutputNames(self.ptr, allocator.ptr, &mut buffer, &mut lengths, &mut count) -> Error::GetBoundOutputs];
+		if count == 0 {
+			return Ok(Vec::new());
+		}
+
+		// names are packed into `buffer` without separators or null terminators
+		let lengths_slice = unsafe { std::slice::from_raw_parts(lengths, count as _) };
+		let mut offset = 0;
+		let names = lengths_slice
+			.iter()
+			.map(|&len| {
+				let len: usize = len as _;
+				let bytes = unsafe { std::slice::from_raw_parts(buffer.add(offset) as *const u8, len) };
+				offset += len;
+				String::from_utf8(bytes.to_vec())
+			})
+			.collect::<std::result::Result<Vec<_>, _>>();
+
+		ortfree!(unsafe allocator.ptr, buffer);
+		ortfree!(unsafe allocator.ptr, lengths);
+		Ok(names?)
+	}
+
+	/// Returns the values of the outputs bound to this binding, in the order they were bound; for outputs bound with
+	/// [`IoBinding::bind_output_to_device`], these are the values allocated by the last run.
+	pub fn bound_output_values(&self) -> Result<Vec<Value>> {
+		let allocator = self.session.allocator();
+		let mut output_values_ptr: *mut *mut ort_sys::OrtValue = ptr::null_mut();
+		let mut count: ort_sys::size_t = 0;
+		ortsys![unsafe GetBoundOutputValues(self.ptr, allocator.ptr, &mut output_values_ptr, &mut count) -> Error::GetBoundOutputs];
+		if count == 0 {
+			return Ok(Vec::new());
+		}
+
+		let output_values = unsafe { std::slice::from_raw_parts(output_values_ptr, count as _) }
+			.iter()
+			.map(|&v| unsafe { Value::from_raw(v, Arc::clone(&self.session.inner)) })
+			.collect();
+		// the values are now owned by the returned `Value`s; only the array holding them needs to be freed
+		ortfree!(unsafe allocator.ptr, output_values_ptr);
+		Ok(output_values)
+	}
+
+	/// Runs the session with the bound inputs & outputs.
+	///
+	/// The returned outputs only borrow the binding until they're dropped, so inputs can be rebound between runs.
+	pub fn run(&self) -> Result<SessionOutputs<'_>> {
 		self.run_inner(None)
 	}
 
-	pub fn run_with_options<'i: 's>(&'i self, run_options: Arc<RunOptions>) -> Result<SessionOutputs<'s>> {
+	pub fn run_with_options(&self, run_options: Arc<RunOptions>) -> Result<SessionOutputs<'_>> {
 		self.run_inner(Some(run_options))
 	}
 
-	fn run_inner<'i: 's>(&'i self, run_options: Option<Arc<RunOptions>>) -> Result<SessionOutputs<'s>> {
+	fn run_inner(&self, run_options: Option<Arc<RunOptions>>) -> Result<SessionOutputs<'_>> {
 		let run_options_ptr = if let Some(run_options) = run_options {
 			run_options.run_options_ptr
 		} else {
 			std::ptr::null_mut()
 		};
-		ortsys![unsafe RunWithBinding(self.session.inner.session_ptr, run_options_ptr, self.ptr) -> Error::SessionRunWithIoBinding];
+		ortsys![unsafe RunWithBinding(self.session.inner.session_ptr, run_options_ptr, self.ptr) -> |e| Error::SessionRunWithIoBinding(self.session.inner.label_run_error(e))];
 
 		let mut count = self.output_names.len() as ort_sys::size_t;
 		if count > 0 {
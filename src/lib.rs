@@ -16,7 +16,10 @@ pub(crate) mod execution_providers;
 pub(crate) mod io_binding;
 pub(crate) mod memory;
 pub(crate) mod metadata;
+pub(crate) mod pool;
+pub(crate) mod preprocess;
 pub(crate) mod session;
+pub(crate) mod sparse;
 pub(crate) mod tensor;
 pub(crate) mod value;
 pub(crate) mod run;
@@ -27,29 +30,38 @@ use std::{
 	ffi::{self, CStr},
 	os::raw::c_char,
 	ptr,
-	sync::{atomic::AtomicPtr, Arc, Mutex, OnceLock}
+	sync::{
+		atomic::{AtomicPtr, AtomicUsize, Ordering},
+		Arc, Mutex, OnceLock
+	}
 };
 
 use tracing::Level;
 
 #[cfg(feature = "load-dynamic")]
-pub use self::environment::init_from;
-pub use self::environment::{init, EnvironmentBuilder, EnvironmentGlobalThreadPoolOptions};
+pub use self::environment::{init_from, init_with_library};
+pub use self::environment::{init, Environment, EnvironmentBuilder, EnvironmentGlobalThreadPoolOptions};
 #[cfg(feature = "fetch-models")]
 #[cfg_attr(docsrs, doc(cfg(feature = "fetch-models")))]
 pub use self::error::FetchModelError;
 pub use self::error::{Error, ErrorInternal, Result};
 pub use self::execution_providers::*;
 pub use self::io_binding::IoBinding;
-pub use self::memory::{AllocationDevice, Allocator, MemoryInfo};
-pub use self::metadata::ModelMetadata;
+pub use self::memory::{AllocationDevice, Allocator, ArenaCfg, MemoryInfo};
+pub use self::metadata::{ModelHeader, ModelMetadata};
+pub use self::pool::{InputPool, PooledValue};
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+pub use self::preprocess::ImageNormalizer;
+pub use self::preprocess::{ModelTokenizer, Preprocessor};
 pub use self::session::{InMemorySession, RunOptions, Session, SessionBuilder, SessionInputs, SessionOutputs, SharedSessionInner};
+pub use self::sparse::{SparseFormat, SparseIndicesFormat, SparseTensor};
 #[cfg(feature = "ndarray")]
 #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
 pub use self::tensor::{ArrayExtensions, ArrayViewHolder, Tensor, TensorData};
 pub use self::tensor::{ExtractTensorData, IntoTensorElementType, TensorElementType};
-pub use self::value::{Value, ValueRef, ValueType};
-pub use self::run::{RustOwnerValue, Values, Names, ONNXTensorElementDataType, get_type_size, convert_to_onnx_el_type};
+pub use self::value::{OnnxType, Value, ValueRef, ValueType};
+pub use self::run::{AllocatedBuffer, RunError, RustOwnerValue, Values, Names, PreparedRun, InputBuilder, TensorTypeAndShape, ONNXTensorElementDataType, check_densely_packed, get_type_size, convert_to_onnx_el_type};
 
 #[cfg(not(all(target_arch = "x86", target_os = "windows")))]
 macro_rules! extern_system_fn {
@@ -115,6 +127,51 @@ pub(crate) fn lib_handle() -> MutexGuard<'static, libloading::Library> {
 		.expect("failed to acquire ONNX Runtime dylib lock; another thread panicked?")
 }
 
+fn api_base() -> *const ort_sys::OrtApiBase {
+	#[cfg(feature = "load-dynamic")]
+	let base: *const ort_sys::OrtApiBase = unsafe {
+		let dylib = lib_handle();
+		let base_getter: libloading::Symbol<unsafe extern "C" fn() -> *const ort_sys::OrtApiBase> = dylib
+			.get(b"OrtGetApiBase")
+			.expect("`OrtGetApiBase` must be present in ONNX Runtime dylib");
+		base_getter()
+	};
+	#[cfg(not(feature = "load-dynamic"))]
+	let base: *const ort_sys::OrtApiBase = unsafe { ort_sys::OrtGetApiBase() };
+	assert_ne!(base, ptr::null());
+	base
+}
+
+/// Returns the version of the ONNX Runtime library `ort` is linked against, i.e. `1.16.3`.
+///
+/// # Panics
+///
+/// Panics if the ONNX Runtime library could not be loaded.
+pub fn ort_version() -> String {
+	let base = api_base();
+	unsafe {
+		let get_version_string: extern_system_fn! { unsafe fn () -> *const ffi::c_char } =
+			(*base).GetVersionString.expect("`GetVersionString` must be present in `OrtApiBase`");
+		CStr::from_ptr(get_version_string()).to_string_lossy().into_owned()
+	}
+}
+
+/// Returns a human-readable summary of the versions of `ort`, `ort-sys`, and the linked ONNX Runtime library, for use
+/// in logs and bug reports.
+///
+/// # Panics
+///
+/// Panics if the ONNX Runtime library could not be loaded.
+pub fn build_info() -> String {
+	format!(
+		"ort {} (ort-sys {}, ONNX Runtime API version {}), ONNX Runtime {}",
+		env!("CARGO_PKG_VERSION"),
+		ort_sys::CRATE_VERSION,
+		ort_sys::ORT_API_VERSION,
+		ort_version()
+	)
+}
+
 pub(crate) static G_ORT_API: OnceLock<Arc<Mutex<AtomicPtr<ort_sys::OrtApi>>>> = OnceLock::new();
 
 /// Attempts to acquire the global [`ort_sys::OrtApi`] object.
@@ -127,17 +184,8 @@ pub fn api() -> ort_sys::OrtApi {
 		.get_or_init(|| {
 			#[cfg(feature = "load-dynamic")]
 			unsafe {
-				let dylib = lib_handle();
-				let base_getter: libloading::Symbol<unsafe extern "C" fn() -> *const ort_sys::OrtApiBase> = dylib
-					.get(b"OrtGetApiBase")
-					.expect("`OrtGetApiBase` must be present in ONNX Runtime dylib");
-				let base: *const ort_sys::OrtApiBase = base_getter();
-				assert_ne!(base, ptr::null());
-
-				let get_version_string: extern_system_fn! { unsafe fn () -> *const ffi::c_char } =
-					(*base).GetVersionString.expect("`GetVersionString` must be present in `OrtApiBase`");
-				let version_string = get_version_string();
-				let version_string = CStr::from_ptr(version_string).to_string_lossy();
+				let base = api_base();
+				let version_string = ort_version();
 				tracing::info!("Using ONNX Runtime version '{version_string}'");
 
 				let lib_minor_version = version_string.split('.').nth(1).map(|x| x.parse::<u32>().unwrap_or(0)).unwrap_or(0);
@@ -158,8 +206,7 @@ pub fn api() -> ort_sys::OrtApi {
 			}
 			#[cfg(not(feature = "load-dynamic"))]
 			{
-				let base: *const ort_sys::OrtApiBase = unsafe { ort_sys::OrtGetApiBase() };
-				assert_ne!(base, ptr::null());
+				let base = api_base();
 				let get_api: extern_system_fn! { unsafe fn(u32) -> *const ort_sys::OrtApi } = unsafe { (*base).GetApi.unwrap() };
 				let api: *const ort_sys::OrtApi = unsafe { get_api(ort_sys::ORT_API_VERSION) };
 				Arc::new(Mutex::new(AtomicPtr::new(api as *mut ort_sys::OrtApi)))
@@ -256,9 +303,58 @@ impl<'a> From<&'a str> for CodeLocation<'a> {
 	}
 }
 
+/// Warnings logged by sessions being created with [`SessionBuilder::strict_model_validation`], as `(token, log ID,
+/// warnings)` for each capture. Captures are keyed by a unique token, since several sessions may share a log ID set
+/// with [`SessionBuilder::with_log_id`].
+static G_CAPTURED_WARNINGS: Mutex<Vec<(usize, String, Vec<String>)>> = Mutex::new(Vec::new());
+
+/// Collects the warnings ONNX Runtime logs for the session with the given log ID until it is finished or dropped.
+pub(crate) struct WarningCapture {
+	token: usize
+}
+
+impl WarningCapture {
+	pub(crate) fn start(log_id: String) -> Self {
+		static NEXT_TOKEN: AtomicUsize = AtomicUsize::new(0);
+		let token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+		G_CAPTURED_WARNINGS.lock().unwrap_or_else(|e| e.into_inner()).push((token, log_id, Vec::new()));
+		Self { token }
+	}
+
+	/// Stops capturing, returning the warnings logged since the capture started.
+	pub(crate) fn finish(self) -> Vec<String> {
+		self.take().unwrap_or_default()
+	}
+
+	/// Removes this capture, if it hasn't been removed already.
+	fn take(&self) -> Option<Vec<String>> {
+		let mut captures = G_CAPTURED_WARNINGS.lock().unwrap_or_else(|e| e.into_inner());
+		let i = captures.iter().position(|(token, ..)| *token == self.token)?;
+		Some(captures.swap_remove(i).2)
+	}
+}
+
+impl Drop for WarningCapture {
+	fn drop(&mut self) {
+		self.take();
+	}
+}
+
+fn capture_warning(log_id: *const c_char, message: &str) {
+	if log_id.is_null() {
+		return;
+	}
+	let log_id = unsafe { CStr::from_ptr(log_id) }.to_bytes();
+	let mut captures = G_CAPTURED_WARNINGS.lock().unwrap_or_else(|e| e.into_inner());
+	// sessions created concurrently with the same log ID can't be told apart, so each of them gets the warning
+	for (_, _, warnings) in captures.iter_mut().filter(|(_, id, _)| id.as_bytes() == log_id) {
+		warnings.push(message.to_owned());
+	}
+}
+
 extern_system_fn! {
 	/// Callback from C that will handle ONNX logging, forwarding ONNX's logs to the `tracing` crate.
-	pub(crate) fn custom_logger(_params: *mut ffi::c_void, severity: ort_sys::OrtLoggingLevel, category: *const c_char, _: *const c_char, code_location: *const c_char, message: *const c_char) {
+	pub(crate) fn custom_logger(_params: *mut ffi::c_void, severity: ort_sys::OrtLoggingLevel, category: *const c_char, log_id: *const c_char, code_location: *const c_char, message: *const c_char) {
 		assert_ne!(category, ptr::null());
 		let category = unsafe { CStr::from_ptr(category) };
 		assert_ne!(code_location, ptr::null());
@@ -266,6 +362,10 @@ extern_system_fn! {
 		assert_ne!(message, ptr::null());
 		let message = unsafe { CStr::from_ptr(message) }.to_str().unwrap();
 
+		if severity as i32 >= ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_WARNING as i32 {
+			capture_warning(log_id, message);
+		}
+
 		let code_location = CodeLocation::from(code_location_str);
 		let span = tracing::span!(
 			Level::TRACE,
@@ -378,7 +478,7 @@ impl From<GraphOptimizationLevel> for ort_sys::GraphOptimizationLevel {
 }
 
 /// Execution provider allocator type.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum AllocatorType {
 	/// Default device-specific allocator.
 	Device,
@@ -395,8 +495,18 @@ impl From<AllocatorType> for ort_sys::OrtAllocatorType {
 	}
 }
 
+impl From<ort_sys::OrtAllocatorType> for AllocatorType {
+	fn from(val: ort_sys::OrtAllocatorType) -> Self {
+		match val {
+			ort_sys::OrtAllocatorType::OrtArenaAllocator => AllocatorType::Arena,
+			// ONNX Runtime never creates memory infos with the invalid allocator type
+			ort_sys::OrtAllocatorType::OrtDeviceAllocator | ort_sys::OrtAllocatorType::OrtInvalidAllocator => AllocatorType::Device
+		}
+	}
+}
+
 /// Memory types for allocated memory.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MemType {
 	/// Any CPU memory used by non-CPU execution provider.
 	CPUInput,
@@ -421,6 +531,16 @@ impl From<MemType> for ort_sys::OrtMemType {
 	}
 }
 
+impl From<ort_sys::OrtMemType> for MemType {
+	fn from(val: ort_sys::OrtMemType) -> Self {
+		match val {
+			ort_sys::OrtMemType::OrtMemTypeCPUInput => MemType::CPUInput,
+			ort_sys::OrtMemType::OrtMemTypeCPUOutput => MemType::CPUOutput,
+			ort_sys::OrtMemType::OrtMemTypeDefault => MemType::Default
+		}
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -431,4 +551,17 @@ mod test {
 		let ptr = s.as_c_str().as_ptr();
 		assert_eq!("foo", char_p_to_string(ptr).unwrap());
 	}
+
+	#[test]
+	fn test_warning_capture_shared_log_id() {
+		let log_id = ffi::CString::new("test-shared-log-id").unwrap();
+		let first = WarningCapture::start("test-shared-log-id".to_owned());
+		let second = WarningCapture::start("test-shared-log-id".to_owned());
+		capture_warning(log_id.as_ptr(), "Removing initializer 'w'");
+		assert_eq!(first.finish(), ["Removing initializer 'w'"]);
+
+		// finishing (and dropping) the first capture must leave the second one in place
+		capture_warning(log_id.as_ptr(), "Removing initializer 'v'");
+		assert_eq!(second.finish(), ["Removing initializer 'w'", "Removing initializer 'v'"]);
+	}
 }
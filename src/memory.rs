@@ -1,4 +1,8 @@
-use std::ffi::{c_char, c_int, CString};
+use std::{
+	ffi::{c_char, c_int, CStr, CString},
+	fmt,
+	sync::OnceLock
+};
 
 use super::{
 	error::{Error, Result},
@@ -7,6 +11,9 @@ use super::{
 use crate::{char_p_to_string, error::status_to_result};
 
 /// An ONNX Runtime allocator, used to manage the allocation of [`crate::Value`]s.
+///
+/// Allocation statistics (`GetAllocatorStats`) are only available from ONNX Runtime 1.18 and are not exposed by the
+/// 1.16 bindings `ort` is built against; use the arena configuration ([`crate::ArenaCfg`]) to bound memory usage instead.
 #[derive(Debug)]
 pub struct Allocator {
 	pub(crate) ptr: *mut ort_sys::OrtAllocator,
@@ -79,7 +86,7 @@ impl TryFrom<&str> for AllocationDevice {
 	}
 }
 
-#[derive(Debug)]
+/// Describes where a tensor's memory resides: the device & device ID, and the allocator & memory type used for it.
 pub struct MemoryInfo {
 	pub(crate) ptr: *mut ort_sys::OrtMemoryInfo,
 	pub(crate) should_release: bool
@@ -114,17 +121,149 @@ impl MemoryInfo {
 		})
 	}
 
+	/// Like [`MemoryInfo::new_cpu`], but for the default arena allocator & memory type, which nearly every tensor
+	/// created from Rust data uses, returns a process-wide memory info instead of creating a new one each time. Other
+	/// combinations are created as usual.
+	pub(crate) fn new_cpu_shared(allocator: AllocatorType, memory_type: MemType) -> Result<Self> {
+		if !matches!((allocator, memory_type), (AllocatorType::Arena, MemType::Default)) {
+			return Self::new_cpu(allocator, memory_type);
+		}
+		let shared = match DEFAULT_CPU_MEMORY_INFO.get() {
+			Some(shared) => shared,
+			None => {
+				// if another thread got there first, ours is dropped & released
+				let _ = DEFAULT_CPU_MEMORY_INFO.set(SharedMemoryInfo(Self::new_cpu(allocator, memory_type)?));
+				DEFAULT_CPU_MEMORY_INFO.get().expect("just set")
+			}
+		};
+		Ok(Self {
+			ptr: shared.0.ptr,
+			should_release: false
+		})
+	}
+
+	/// Returns the memory info of the tensor value `value_ptr`. The returned [`MemoryInfo`] is owned by the value and
+	/// will not be released when dropped, so it must not outlive the value; see [`MemoryInfo::copy_from_value`] for one
+	/// that may be handed out.
+	pub(crate) fn from_value(value_ptr: *const ort_sys::OrtValue) -> Result<Self> {
+		let mut memory_info_ptr: *const ort_sys::OrtMemoryInfo = std::ptr::null();
+		ortsys![unsafe GetTensorMemoryInfo(value_ptr, &mut memory_info_ptr) -> Error::GetTensorMemoryInfo; nonNull(memory_info_ptr)];
+		Ok(Self {
+			ptr: memory_info_ptr as *mut _,
+			should_release: false
+		})
+	}
+
+	/// Returns an owned copy of the memory info of the tensor value `value_ptr`, which stays valid after the value is
+	/// released.
+	pub(crate) fn copy_from_value(value_ptr: *const ort_sys::OrtValue) -> Result<Self> {
+		Self::from_value(value_ptr)?.try_clone()
+	}
+
 	/// Returns the [`AllocationDevice`] this memory info
 	pub fn allocation_device(&self) -> Result<AllocationDevice> {
+		let name: String = char_p_to_string(self.device_name()?.as_ptr())?;
+		AllocationDevice::try_from(name.as_str()).map_err(Error::UnknownAllocationDevice)
+	}
+
+	/// Returns ONNX Runtime's name for the device, i.e. `Cpu` or `Cuda`.
+	fn device_name(&self) -> Result<&CStr> {
 		let mut name_ptr: *const c_char = std::ptr::null_mut();
 		ortsys![unsafe MemoryInfoGetName(self.ptr, &mut name_ptr) -> Error::GetAllocationDevice; nonNull(name_ptr)];
 		// no need to free: "Do NOT free the returned pointer. It is valid for the lifetime of the OrtMemoryInfo"
+		Ok(unsafe { CStr::from_ptr(name_ptr) })
+	}
 
-		let name: String = char_p_to_string(name_ptr)?;
-		AllocationDevice::try_from(name.as_str()).map_err(Error::UnknownAllocationDevice)
+	/// Returns the ID of the device this memory resides on, i.e. the CUDA device ID.
+	pub fn device_id(&self) -> Result<i32> {
+		let mut device_id: c_int = 0;
+		ortsys![unsafe MemoryInfoGetId(self.ptr, &mut device_id) -> Error::GetMemoryInfo];
+		Ok(device_id as _)
+	}
+
+	/// Returns the [`MemType`] of this memory.
+	pub fn memory_type(&self) -> Result<MemType> {
+		let mut memory_type = ort_sys::OrtMemType::OrtMemTypeDefault;
+		ortsys![unsafe MemoryInfoGetMemType(self.ptr, &mut memory_type) -> Error::GetMemoryInfo];
+		Ok(memory_type.into())
+	}
+
+	/// Returns the [`AllocatorType`] this memory is allocated with.
+	pub fn allocator_type(&self) -> Result<AllocatorType> {
+		let mut allocator_type = ort_sys::OrtAllocatorType::OrtDeviceAllocator;
+		ortsys![unsafe MemoryInfoGetType(self.ptr, &mut allocator_type) -> Error::GetMemoryInfo];
+		Ok(allocator_type.into())
+	}
+
+	/// Returns `true` if this memory is host memory the CPU can read directly. This includes pinned memory like
+	/// [`AllocationDevice::CUDAPinned`], which ONNX Runtime treats as CPU memory.
+	pub fn is_cpu_accessible(&self) -> bool {
+		let mut device_type = ort_sys::OrtMemoryInfoDeviceType::OrtMemoryInfoDeviceType_CPU;
+		ortsys![unsafe MemoryInfoGetDeviceType(self.ptr, &mut device_type)];
+		device_type == ort_sys::OrtMemoryInfoDeviceType::OrtMemoryInfoDeviceType_CPU
+	}
+}
+
+impl fmt::Debug for MemoryInfo {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut debug = f.debug_struct("MemoryInfo");
+		if let Ok(name) = self.device_name() {
+			debug.field("device", &name);
+		}
+		if let Ok(device_id) = self.device_id() {
+			debug.field("device_id", &device_id);
+		}
+		if let Ok(memory_type) = self.memory_type() {
+			debug.field("memory_type", &memory_type);
+		}
+		if let Ok(allocator_type) = self.allocator_type() {
+			debug.field("allocator_type", &allocator_type);
+		}
+		debug.finish()
 	}
 }
 
+/// Creates a new `OrtMemoryInfo` from the queried device, device ID, allocator & memory type.
+///
+/// Copying the pointer instead would be unsound: an owned memory info releases its `OrtMemoryInfo` when dropped, so
+/// the original and the copy would both release it (a double free), and memory infos borrowed internally from a value
+/// would leave the copy dangling once the value is dropped. The clone is always owned, regardless of whether `self`
+/// is.
+///
+/// # Panics
+/// Panics if ONNX Runtime fails to query or create the memory info.
+impl Clone for MemoryInfo {
+	fn clone(&self) -> Self {
+		self.try_clone().expect("failed to clone `MemoryInfo`")
+	}
+}
+
+impl MemoryInfo {
+	fn try_clone(&self) -> Result<Self> {
+		let name = self.device_name()?;
+		let (device_id, memory_type, allocator_type) = (self.device_id()?, self.memory_type()?, self.allocator_type()?);
+		let mut memory_info_ptr: *mut ort_sys::OrtMemoryInfo = std::ptr::null_mut();
+		ortsys![
+			unsafe CreateMemoryInfo(name.as_ptr(), allocator_type.into(), device_id, memory_type.into(), &mut memory_info_ptr)
+				-> Error::CreateMemoryInfo;
+			nonNull(memory_info_ptr)
+		];
+		Ok(Self {
+			ptr: memory_info_ptr,
+			should_release: true
+		})
+	}
+}
+
+/// The CPU memory info returned by [`MemoryInfo::new_cpu_shared`]; never released.
+static DEFAULT_CPU_MEMORY_INFO: OnceLock<SharedMemoryInfo> = OnceLock::new();
+
+struct SharedMemoryInfo(MemoryInfo);
+
+// an `OrtMemoryInfo` is never modified after it is created
+unsafe impl Send for SharedMemoryInfo {}
+unsafe impl Sync for SharedMemoryInfo {}
+
 impl Drop for MemoryInfo {
 	#[tracing::instrument]
 	fn drop(&mut self) {
@@ -136,6 +275,43 @@ impl Drop for MemoryInfo {
 	}
 }
 
+/// Configuration for an arena allocator, used with [`Environment::create_and_register_allocator`].
+///
+/// [`Environment::create_and_register_allocator`]: crate::Environment::create_and_register_allocator
+#[derive(Debug)]
+pub struct ArenaCfg {
+	pub(crate) ptr: *mut ort_sys::OrtArenaCfg
+}
+
+impl ArenaCfg {
+	/// Creates a new arena configuration.
+	///
+	/// - `initial_chunk_bytes` is the size of the first chunk the arena allocates.
+	/// - `max_dead_bytes_per_chunk` is the threshold of unused bytes in a chunk above which the chunk is split.
+	/// - `initial_growth_chunk_bytes` is the size of the first chunk allocated after the arena is exhausted.
+	pub fn new(initial_chunk_bytes: usize, max_dead_bytes_per_chunk: usize, initial_growth_chunk_bytes: usize) -> Result<Self> {
+		let keys = [
+			CString::new("initial_chunk_size_bytes").unwrap(),
+			CString::new("max_dead_bytes_per_chunk").unwrap(),
+			CString::new("initial_growth_chunk_size_bytes").unwrap()
+		];
+		let key_ptrs = keys.iter().map(|k| k.as_ptr()).collect::<Vec<_>>();
+		let values: [ort_sys::size_t; 3] = [initial_chunk_bytes as _, max_dead_bytes_per_chunk as _, initial_growth_chunk_bytes as _];
+		let mut arena_cfg_ptr: *mut ort_sys::OrtArenaCfg = std::ptr::null_mut();
+		ortsys![unsafe CreateArenaCfgV2(key_ptrs.as_ptr(), values.as_ptr(), keys.len() as _, &mut arena_cfg_ptr) -> Error::CreateArenaCfg; nonNull(arena_cfg_ptr)];
+		Ok(Self { ptr: arena_cfg_ptr })
+	}
+}
+
+impl Drop for ArenaCfg {
+	fn drop(&mut self) {
+		if !self.ptr.is_null() {
+			ortsys![unsafe ReleaseArenaCfg(self.ptr)];
+		}
+		self.ptr = std::ptr::null_mut();
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use test_log::test;
@@ -147,4 +323,46 @@ mod tests {
 		let memory_info = MemoryInfo::new_cpu(AllocatorType::Device, MemType::Default).unwrap();
 		std::mem::drop(memory_info);
 	}
+
+	#[test]
+	fn shared_memory_info() {
+		let a = MemoryInfo::new_cpu_shared(AllocatorType::Arena, MemType::Default).unwrap();
+		let b = MemoryInfo::new_cpu_shared(AllocatorType::Arena, MemType::Default).unwrap();
+		assert_eq!(a.ptr, b.ptr);
+		assert!(!a.should_release && !b.should_release);
+		// dropping the handles must not release the shared memory info
+		drop((a, b));
+		let c = MemoryInfo::new_cpu_shared(AllocatorType::Arena, MemType::Default).unwrap();
+		assert_eq!(c.allocation_device().unwrap(), AllocationDevice::CPU);
+		assert!(c.is_cpu_accessible());
+
+		let device = MemoryInfo::new_cpu_shared(AllocatorType::Device, MemType::Default).unwrap();
+		assert_ne!(device.ptr, c.ptr);
+		assert!(device.should_release);
+	}
+
+	#[test]
+	fn clone_memory_info() {
+		let shared = MemoryInfo::new_cpu_shared(AllocatorType::Arena, MemType::Default).unwrap();
+		let clone = shared.clone();
+		assert_ne!(clone.ptr, shared.ptr);
+		assert!(clone.should_release);
+		assert_eq!(clone.allocation_device().unwrap(), AllocationDevice::CPU);
+		assert_eq!(clone.device_id().unwrap(), 0);
+		assert_eq!(clone.memory_type().unwrap(), MemType::Default);
+		assert_eq!(clone.allocator_type().unwrap(), AllocatorType::Arena);
+		assert_eq!(format!("{clone:?}"), format!("{shared:?}"));
+		assert_eq!(
+			format!("{shared:?}"),
+			r#"MemoryInfo { device: "Cpu", device_id: 0, memory_type: Default, allocator_type: Arena }"#
+		);
+		drop(shared);
+		drop(clone);
+
+		let device = MemoryInfo::new_cpu(AllocatorType::Device, MemType::CPUInput).unwrap();
+		let clone = device.clone();
+		drop(device);
+		assert_eq!(clone.memory_type().unwrap(), MemType::CPUInput);
+		assert_eq!(clone.allocator_type().unwrap(), AllocatorType::Device);
+	}
 }
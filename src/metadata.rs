@@ -1,8 +1,18 @@
-use std::{ffi::CString, os::raw::c_char};
+use std::{
+	collections::HashMap,
+	ffi::CString,
+	fs::File,
+	io::{self, BufReader, Cursor, Read, Seek, SeekFrom},
+	os::raw::c_char,
+	path::Path
+};
 
 use super::{char_p_to_string, error::Result, ortfree, ortsys, Error};
 
 /// Container for model metadata, including name & producer information.
+///
+/// ONNX Runtime's metadata API does not include the model's IR version, producer version or opset imports, so they
+/// can't be queried here; read them from the model file with [`ModelHeader`] instead.
 pub struct ModelMetadata {
 	metadata_ptr: *mut ort_sys::OrtModelMetadata,
 	allocator_ptr: *mut ort_sys::OrtAllocator
@@ -87,6 +97,39 @@ impl ModelMetadata {
 			Ok(None)
 		}
 	}
+
+	/// Gets the keys of the model's custom metadata. See [`ModelMetadata::custom`] to look up their values, or
+	/// [`ModelMetadata::custom_map`] to get all keys & values at once.
+	pub fn custom_keys(&self) -> Result<Vec<String>> {
+		let mut keys_ptr: *mut *mut c_char = std::ptr::null_mut();
+		let mut num_keys = 0i64;
+		ortsys![unsafe ModelMetadataGetCustomMetadataMapKeys(self.metadata_ptr, self.allocator_ptr, &mut keys_ptr, &mut num_keys) -> Error::GetModelMetadata];
+		// ONNX Runtime doesn't allocate the array if there are no keys
+		if keys_ptr.is_null() || num_keys <= 0 {
+			return Ok(Vec::new());
+		}
+
+		// every key & the array itself must be freed, even if a key fails to convert
+		let key_ptrs = unsafe { std::slice::from_raw_parts(keys_ptr, num_keys as usize) };
+		let keys = key_ptrs.iter().map(|&key_ptr| char_p_to_string(key_ptr)).collect::<Result<Vec<_>>>();
+		for &key_ptr in key_ptrs {
+			ortfree!(unsafe self.allocator_ptr, key_ptr);
+		}
+		ortfree!(unsafe self.allocator_ptr, keys_ptr);
+		keys
+	}
+
+	/// Gets all of the model's custom metadata as a map of keys to values.
+	pub fn custom_map(&self) -> Result<HashMap<String, String>> {
+		let mut map = HashMap::new();
+		for key in self.custom_keys()? {
+			// a key that was just listed should always have a value
+			if let Some(value) = self.custom(&key)? {
+				map.insert(key, value);
+			}
+		}
+		Ok(map)
+	}
 }
 
 impl Drop for ModelMetadata {
@@ -94,3 +137,164 @@ impl Drop for ModelMetadata {
 		ortsys![unsafe ReleaseModelMetadata(self.metadata_ptr)];
 	}
 }
+
+/// The top-level fields of an ONNX model's protobuf that ONNX Runtime's metadata API doesn't expose, i.e. for auditing
+/// which exporter & IR version a fleet of models was produced with.
+///
+/// These are read directly from the model file without loading it into a session. The model's graph is skipped over
+/// rather than read, so this is cheap even for large models.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelHeader {
+	/// The version of the ONNX IR the model uses.
+	pub ir_version: i64,
+	/// The name of the tool that produced the model, i.e. `pytorch`; also available as [`ModelMetadata::producer`].
+	pub producer_name: String,
+	/// The version of the tool that produced the model, i.e. `2.1.0`.
+	pub producer_version: String,
+	/// The model's domain; also available from [`ModelMetadata`].
+	pub domain: String,
+	/// The version of the model itself; also available as [`ModelMetadata::version`].
+	pub model_version: i64,
+	/// The operator sets the model imports, as `(domain, version)` pairs; the default ONNX domain is `""`.
+	pub opset_imports: Vec<(String, i64)>
+}
+
+impl ModelHeader {
+	/// Reads the header of the ONNX model at `path`.
+	pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+		let file = File::open(path).map_err(Error::ReadModelHeader)?;
+		Self::from_reader(BufReader::new(file))
+	}
+
+	/// Reads the header of an ONNX model held in memory.
+	pub fn from_bytes(model: &[u8]) -> Result<Self> {
+		Self::from_reader(Cursor::new(model))
+	}
+
+	fn from_reader(mut reader: impl Read + Seek) -> Result<Self> {
+		let mut header = ModelHeader::default();
+		while let Some((field, value)) = read_field(&mut reader, |field| matches!(field, 2 | 3 | 4 | 8)).map_err(Error::ReadModelHeader)? {
+			match (field, value) {
+				(1, FieldValue::Varint(value)) => header.ir_version = value as i64,
+				(2, FieldValue::Bytes(value)) => header.producer_name = protobuf_string(value)?,
+				(3, FieldValue::Bytes(value)) => header.producer_version = protobuf_string(value)?,
+				(4, FieldValue::Bytes(value)) => header.domain = protobuf_string(value)?,
+				(5, FieldValue::Varint(value)) => header.model_version = value as i64,
+				(8, FieldValue::Bytes(value)) => {
+					let (mut domain, mut version) = (String::new(), 0);
+					let mut opset = Cursor::new(value);
+					while let Some((field, value)) = read_field(&mut opset, |field| field == 1).map_err(Error::ReadModelHeader)? {
+						match (field, value) {
+							(1, FieldValue::Bytes(value)) => domain = protobuf_string(value)?,
+							(2, FieldValue::Varint(value)) => version = value as i64,
+							_ => {}
+						}
+					}
+					header.opset_imports.push((domain, version));
+				}
+				_ => {}
+			}
+		}
+		Ok(header)
+	}
+}
+
+enum FieldValue {
+	Varint(u64),
+	Bytes(Vec<u8>),
+	Skipped
+}
+
+/// Reads the next field of a protobuf message, returning `None` at the end of the message. The contents of
+/// length-delimited fields are only read if `read_bytes` returns `true` for the field number; others are skipped.
+fn read_field(reader: &mut (impl Read + Seek), read_bytes: impl Fn(u64) -> bool) -> io::Result<Option<(u64, FieldValue)>> {
+	let Some(key) = read_varint(reader)? else {
+		return Ok(None);
+	};
+	let field = key >> 3;
+	let value = match key & 0x7 {
+		0 => FieldValue::Varint(read_varint(reader)?.ok_or(io::ErrorKind::UnexpectedEof)?),
+		1 => {
+			reader.seek(SeekFrom::Current(8))?;
+			FieldValue::Skipped
+		}
+		2 => {
+			let len = read_varint(reader)?.ok_or(io::ErrorKind::UnexpectedEof)?;
+			if read_bytes(field) {
+				let mut bytes = Vec::new();
+				reader.take(len).read_to_end(&mut bytes)?;
+				if bytes.len() as u64 != len {
+					return Err(io::ErrorKind::UnexpectedEof.into());
+				}
+				FieldValue::Bytes(bytes)
+			} else {
+				let len = i64::try_from(len).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "field length out of range"))?;
+				reader.seek(SeekFrom::Current(len))?;
+				FieldValue::Skipped
+			}
+		}
+		5 => {
+			reader.seek(SeekFrom::Current(4))?;
+			FieldValue::Skipped
+		}
+		wire_type => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unsupported protobuf wire type {wire_type}")))
+	};
+	Ok(Some((field, value)))
+}
+
+/// Reads a base-128 varint, returning `None` if the reader is at its end.
+fn read_varint(reader: &mut impl Read) -> io::Result<Option<u64>> {
+	let mut value = 0u64;
+	for i in 0..10 {
+		let mut byte = [0u8];
+		if reader.read(&mut byte)? == 0 {
+			return if i == 0 { Ok(None) } else { Err(io::ErrorKind::UnexpectedEof.into()) };
+		}
+		value |= ((byte[0] & 0x7f) as u64) << (7 * i);
+		if byte[0] & 0x80 == 0 {
+			return Ok(Some(value));
+		}
+	}
+	Err(io::Error::new(io::ErrorKind::InvalidData, "varint is too long"))
+}
+
+fn protobuf_string(bytes: Vec<u8>) -> Result<String> {
+	String::from_utf8(bytes).map_err(|e| Error::ReadModelHeader(io::Error::new(io::ErrorKind::InvalidData, e)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_model_header() {
+		// ir_version: 8, producer_name: "pytorch", producer_version: "2.1.0", graph: { name: "g" }, a float field to
+		// skip, opset_import: [{ domain: "", version: 17 }, { domain: "ai.onnx.ml", version: 3 }]
+		let mut model = vec![0x08, 8, 0x12, 7];
+		model.extend_from_slice(b"pytorch");
+		model.extend_from_slice(&[0x1a, 5]);
+		model.extend_from_slice(b"2.1.0");
+		model.extend_from_slice(&[0x3a, 3, 0x12, 1, b'g']);
+		model.extend_from_slice(&[0x4d, 0, 0, 0, 0]);
+		model.extend_from_slice(&[0x42, 4, 0x0a, 0, 0x10, 17]);
+		model.extend_from_slice(&[0x42, 14, 0x0a, 10]);
+		model.extend_from_slice(b"ai.onnx.ml");
+		model.extend_from_slice(&[0x10, 3]);
+
+		let header = ModelHeader::from_bytes(&model).unwrap();
+		assert_eq!(
+			header,
+			ModelHeader {
+				ir_version: 8,
+				producer_name: "pytorch".to_owned(),
+				producer_version: "2.1.0".to_owned(),
+				domain: String::new(),
+				model_version: 0,
+				opset_imports: vec![(String::new(), 17), ("ai.onnx.ml".to_owned(), 3)]
+			}
+		);
+
+		assert!(matches!(ModelHeader::from_bytes(&model[..model.len() - 3]), Err(Error::ReadModelHeader(_))));
+		assert!(matches!(ModelHeader::from_bytes(&[0x0b]), Err(Error::ReadModelHeader(_))));
+	}
+}
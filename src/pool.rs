@@ -0,0 +1,124 @@
+use std::{
+	collections::HashMap,
+	fmt::Debug,
+	ops::{Deref, DerefMut},
+	sync::{Mutex, PoisonError}
+};
+
+use crate::{IntoTensorElementType, RunError, RustOwnerValue};
+
+/// Free values, by shape.
+type FreeValues<T> = HashMap<Vec<i64>, Vec<RustOwnerValue<Vec<T>>>>;
+
+/// A pool of [`RustOwnerValue`]s, keyed by shape, for reusing input buffers across runs instead of allocating a new
+/// `Vec` and creating a new tensor for every request.
+///
+/// Values are handed out by [`InputPool::acquire`] and returned to the pool when the [`PooledValue`] guard is dropped.
+/// A recycled value still holds the data it was last filled with, so callers should overwrite it entirely.
+///
+/// ```no_run
+/// # use ort::InputPool;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let pool = InputPool::<f32>::new();
+/// for _ in 0..16 {
+/// 	let mut input = pool.acquire(&[1, 3, 224, 224])?;
+/// 	input.as_mut_slice().fill(0.5);
+/// 	// ... run the session with `input` ...
+/// } // `input` is returned to the pool here
+/// # Ok(())
+/// # }
+/// ```
+pub struct InputPool<T> {
+	free: Mutex<FreeValues<T>>,
+	max_per_shape: usize
+}
+
+impl<T> InputPool<T> {
+	/// Creates a new, empty pool, keeping up to 4 free values per shape.
+	pub fn new() -> Self {
+		Self::with_max_per_shape(4)
+	}
+
+	/// Creates a new, empty pool, keeping up to `max_per_shape` free values per shape; values returned to the pool
+	/// beyond that are dropped.
+	pub fn with_max_per_shape(max_per_shape: usize) -> Self {
+		Self {
+			free: Mutex::new(HashMap::new()),
+			max_per_shape
+		}
+	}
+
+	/// Drops all free values held by the pool.
+	pub fn clear(&self) {
+		self.free.lock().unwrap_or_else(PoisonError::into_inner).clear();
+	}
+
+	fn release(&self, shape: Vec<i64>, value: RustOwnerValue<Vec<T>>) {
+		let mut free = self.free.lock().unwrap_or_else(PoisonError::into_inner);
+		let values = free.entry(shape).or_default();
+		if values.len() < self.max_per_shape {
+			values.push(value);
+		}
+	}
+}
+
+impl<T> InputPool<T>
+where
+	T: IntoTensorElementType + Debug + Clone + Default + 'static
+{
+	/// Takes a free value of the given shape from the pool, or creates a new zero-filled one if there are none.
+	pub fn acquire(&self, shape: &[i64]) -> crate::Result<PooledValue<'_, T>, RunError> {
+		let recycled = self.free.lock().unwrap_or_else(PoisonError::into_inner).get_mut(shape).and_then(Vec::pop);
+		let value = match recycled {
+			Some(value) => value,
+			None => RustOwnerValue::zeros(shape)?
+		};
+		Ok(PooledValue {
+			pool: self,
+			shape: shape.to_vec(),
+			value: Some(value)
+		})
+	}
+}
+
+impl<T> Default for InputPool<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// A [`RustOwnerValue`] borrowed from an [`InputPool`], returned to the pool when dropped.
+pub struct PooledValue<'p, T> {
+	pool: &'p InputPool<T>,
+	shape: Vec<i64>,
+	value: Option<RustOwnerValue<Vec<T>>>
+}
+
+impl<'p, T> PooledValue<'p, T> {
+	/// Takes the value out of the pool for good; it will not be returned when dropped.
+	pub fn detach(mut self) -> RustOwnerValue<Vec<T>> {
+		self.value.take().expect("value is only taken on detach or drop")
+	}
+}
+
+impl<'p, T> Deref for PooledValue<'p, T> {
+	type Target = RustOwnerValue<Vec<T>>;
+
+	fn deref(&self) -> &Self::Target {
+		self.value.as_ref().expect("value is only taken on detach or drop")
+	}
+}
+
+impl<'p, T> DerefMut for PooledValue<'p, T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.value.as_mut().expect("value is only taken on detach or drop")
+	}
+}
+
+impl<'p, T> Drop for PooledValue<'p, T> {
+	fn drop(&mut self) {
+		if let Some(value) = self.value.take() {
+			self.pool.release(std::mem::take(&mut self.shape), value);
+		}
+	}
+}
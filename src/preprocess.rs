@@ -0,0 +1,117 @@
+//! Reusable preprocessing steps that turn raw input bytes into tensors ready to be passed to a session.
+
+use std::sync::Arc;
+
+use crate::{RunError, RustOwnerValue, Session, TensorElementType, Value};
+
+/// Turns raw input bytes, such as an image or a piece of text, into a tensor ready to be fed to a session.
+///
+/// Implementors bundle the steps a model expects of its input (decoding, resizing, normalizing, tokenizing...) so
+/// they can be reused across models and composed into pipelines.
+pub trait Preprocessor {
+	/// Processes `raw` into an input tensor.
+	fn process(&self, raw: &[u8]) -> crate::Result<RustOwnerValue<Vec<f32>>, RunError>;
+}
+
+/// Normalizes a packed RGB8 image into a `[1, 3, H, W]` (NCHW) tensor, as with [`RustOwnerValue::from_image`],
+/// optionally resizing it first.
+///
+/// `raw` holds the `width * height` pixels of the image row by row, three bytes per pixel; decode compressed formats
+/// such as PNG or JPEG before processing them.
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+#[derive(Debug, Clone)]
+pub struct ImageNormalizer {
+	width: u32,
+	height: u32,
+	mean: [f32; 3],
+	std: [f32; 3],
+	resize: Option<(u32, u32)>
+}
+
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+impl ImageNormalizer {
+	/// Creates a normalizer for images of `width` x `height` pixels. `mean` and `std` are on the `[0, 1]` scale; see
+	/// [`RustOwnerValue::from_image`].
+	pub fn new(width: u32, height: u32, mean: [f32; 3], std: [f32; 3]) -> Self {
+		Self { width, height, mean, std, resize: None }
+	}
+
+	/// Resizes images to `width` x `height` pixels (with a triangle filter) before normalizing them.
+	pub fn resize_to(mut self, width: u32, height: u32) -> Self {
+		self.resize = Some((width, height));
+		self
+	}
+}
+
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+impl Preprocessor for ImageNormalizer {
+	fn process(&self, raw: &[u8]) -> crate::Result<RustOwnerValue<Vec<f32>>, RunError> {
+		let expected = self.width as usize * self.height as usize * 3;
+		if raw.len() != expected {
+			return Err(RunError::Msg(format!(
+				"expected {expected} bytes for a {}x{} RGB image, got {}",
+				self.width,
+				self.height,
+				raw.len()
+			)));
+		}
+		let img = image::RgbImage::from_raw(self.width, self.height, raw.to_vec()).expect("length checked above");
+		let img = match self.resize {
+			Some((width, height)) => image::imageops::resize(&img, width, height, image::imageops::FilterType::Triangle),
+			None => img
+		};
+		RustOwnerValue::from_image(&img, self.mean, self.std)
+	}
+}
+
+/// Tokenizes UTF-8 text by running it through a tokenizer model, such as one exported with
+/// [onnxruntime-extensions](https://github.com/microsoft/onnxruntime-extensions), whose custom ops must be registered
+/// with `SessionBuilder::with_custom_ops_library`.
+///
+/// The text is passed to the model's only input as a string tensor of shape `[1]`. The model's first output, which
+/// must be a tensor of `f32`, `i32` or `i64` (i.e. token IDs), is returned converted to `f32`.
+#[derive(Debug)]
+pub struct ModelTokenizer<'s> {
+	session: &'s Session
+}
+
+impl<'s> ModelTokenizer<'s> {
+	/// Creates a tokenizer running the tokenizer model loaded in `session`.
+	pub fn new(session: &'s Session) -> Self {
+		Self { session }
+	}
+}
+
+impl Preprocessor for ModelTokenizer<'_> {
+	fn process(&self, raw: &[u8]) -> crate::Result<RustOwnerValue<Vec<f32>>, RunError> {
+		let text = std::str::from_utf8(raw).map_err(|e| RunError::Msg(format!("tokenizer input is not valid UTF-8: {e}")))?;
+		let input = Value::from_string_array(self.session.allocator(), (vec![1], Arc::new(vec![text.to_owned()].into_boxed_slice())))?;
+		let outputs = self.session.run([input])?;
+		let output = &outputs[0];
+		let (shape, data) = match output.tensor_element_type()? {
+			TensorElementType::Float32 => {
+				let (shape, data) = output.extract_raw_tensor::<f32>()?;
+				(shape, data.to_vec())
+			}
+			TensorElementType::Int32 => {
+				let (shape, data) = output.extract_raw_tensor::<i32>()?;
+				(shape, data.iter().map(|&id| id as f32).collect())
+			}
+			TensorElementType::Int64 => {
+				let (shape, data) = output.extract_raw_tensor::<i64>()?;
+				(shape, data.iter().map(|&id| id as f32).collect())
+			}
+			actual => {
+				return Err(crate::Error::DataTypeMismatch {
+					actual,
+					requested: TensorElementType::Float32
+				}
+				.into());
+			}
+		};
+		RustOwnerValue::new(&shape, data)
+	}
+}
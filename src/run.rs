@@ -4,8 +4,9 @@ use std::sync::Arc;
 
 pub use ort_sys::ONNXTensorElementDataType;
 
-use crate::{AllocatorType, IntoTensorElementType, MemoryInfo, MemType, ortsys, RunOptions};
+use crate::{Allocator, AllocatorType, IntoTensorElementType, MemoryInfo, MemType, ortsys, RunOptions, SessionOutputs, TensorElementType, Value};
 use crate::error::assert_non_null_pointer;
+use crate::value::{extract_data_type_from_tensor_info, ValueType};
 
 #[derive(Debug, thiserror::Error)]
 pub enum RunError {
@@ -13,6 +14,32 @@ pub enum RunError {
     OrtError(#[from] crate::Error),
     #[error("error msg: {0}")]
     Msg(String),
+    /// An ONNX Runtime error that occurred while performing `op`, i.e. `creating Float32 tensor of shape [1, 3]`.
+    #[error("error while {op}: {source}")]
+    Context {
+        op: String,
+        #[source]
+        source: Box<crate::Error>,
+    },
+}
+
+impl RunError {
+    /// Returns a function wrapping an [`Error`](crate::Error) with a description of the operation that failed.
+    pub(crate) fn context(op: impl Into<String>) -> impl FnOnce(crate::Error) -> RunError {
+        move |source| RunError::Context { op: op.into(), source: Box::new(source) }
+    }
+}
+
+/// The type & shape of a [`RustOwnerValue`], as returned by [`RustOwnerValue::type_and_shape`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TensorTypeAndShape {
+    /// The type of the tensor's elements.
+    pub element_type: TensorElementType,
+    /// The tensor's dimensions; `-1` for a symbolic dimension.
+    pub dimensions: Vec<i64>,
+    /// The total number of elements in the tensor.
+    pub element_count: usize,
 }
 
 /// allow &[T] or &mut [T] or Vec<T> or Box<[T]> or Arc<[T]>
@@ -20,22 +47,155 @@ pub struct RustOwnerValue<Container> {
     ptr: *mut ort_sys::OrtValue,
     owner: Container,
     _memory_info: MemoryInfo,
+    /// `false` if the container owns the `OrtValue` instead, i.e. an [`AllocatedBuffer`].
+    release_value: bool,
 }
 
+// The `OrtValue` only refers to the container's data, which moves with it.
+unsafe impl<Container: Send> Send for RustOwnerValue<Container> {}
+
 impl<Container> Drop for RustOwnerValue<Container> {
     fn drop(&mut self) {
-        ortsys![unsafe ReleaseValue(self.ptr)];
+        if self.release_value {
+            ortsys![unsafe ReleaseValue(self.ptr)];
+        }
     }
 }
 
 impl<Container> RustOwnerValue<Container> {
     pub fn into_container(mut self) -> Container {
-        ortsys![unsafe ReleaseValue(self.ptr)];
+        if self.release_value {
+            ortsys![unsafe ReleaseValue(self.ptr)];
+        }
         let _memory_info = std::mem::replace(&mut self._memory_info, unsafe { std::mem::zeroed() });
         let owner = std::mem::replace(&mut self.owner, unsafe { std::mem::zeroed() });
         std::mem::forget(self);
         owner
     }
+
+    /// Returns the [`MemoryInfo`] describing where ONNX Runtime sees this tensor's data. The memory info is a copy, so
+    /// it remains valid after this value is dropped.
+    pub fn memory_info(&self) -> crate::Result<MemoryInfo> {
+        MemoryInfo::copy_from_value(self.ptr)
+    }
+
+    /// Returns a pointer to the tensor data as seen by ONNX Runtime (via `GetTensorMutableData`), after checking
+    /// that the tensor's element type is `T`.
+    ///
+    /// # Safety
+    ///
+    /// For values created from a Rust container, the returned pointer aliases the container: it must not be written
+    /// through while a reference obtained from [`RustOwnerValue::as_slice`] or [`RustOwnerValue::as_mut_slice`] is
+    /// alive, and must not be written through at all if the container is immutable (e.g. created with
+    /// [`RustOwnerValue::new`] from a `&[T]`). The pointer is only valid while `self` is alive.
+    pub unsafe fn data_ptr<T: IntoTensorElementType>(&self) -> crate::Result<*mut T> {
+        tensor_data_ptr(self.ptr)
+    }
+
+    /// Copies this tensor's elements into a new `Vec<T>`, after checking that the tensor's element type is `T`.
+    ///
+    /// A [`RustOwnerValue`] is always backed by host memory, so this is a plain copy; see [`Value::to_cpu_vec`] for
+    /// values that may reside on a device. For values created with [`RustOwnerValue::with_any_type`], the bytes must
+    /// also be aligned for `T`, and hold only `0` or `1` for `bool`.
+    pub fn to_cpu_vec<T: IntoTensorElementType + Clone>(&self) -> crate::Result<Vec<T>> {
+        let TensorTypeAndShape { element_type: actual, element_count, .. } = self.type_and_shape()?;
+        let requested = T::into_tensor_element_type();
+        if actual != requested {
+            return Err(crate::Error::DataTypeMismatch { actual, requested });
+        }
+        if element_count == 0 {
+            return Ok(Vec::new());
+        }
+        let data_ptr = unchecked_tensor_data_ptr::<T>(self.ptr, element_count)?;
+        if data_ptr as usize % std::mem::align_of::<T>() != 0 {
+            return Err(crate::Error::MisalignedTensorData);
+        }
+        if T::into_tensor_element_type() == TensorElementType::Bool {
+            let bytes = unsafe { std::slice::from_raw_parts(data_ptr as *const u8, element_count) };
+            if bytes.iter().any(|&b| b > 1) {
+                return Err(crate::Error::InvalidBoolTensorData);
+            }
+        }
+        // SAFETY: the element type, alignment and, for `bool`, the contents were checked above.
+        Ok(unsafe { std::slice::from_raw_parts(data_ptr as *const T, element_count) }.to_vec())
+    }
+
+    /// Returns the element type of this tensor.
+    pub fn element_type(&self) -> crate::Result<TensorElementType> {
+        tensor_element_type(self.ptr)
+    }
+
+    /// Returns the type & shape of this tensor.
+    pub fn dtype(&self) -> crate::Result<ValueType> {
+        self.with_type_and_shape(|tensor_info_ptr| unsafe { extract_data_type_from_tensor_info(tensor_info_ptr) })
+    }
+
+    /// Returns `true` if this tensor can be fed where a value of type `other` is expected, e.g. the `input_type` of one
+    /// of a session's [`inputs`](crate::Session::inputs). See [`ValueType::is_compatible_with`].
+    pub fn is_compatible_with(&self, other: &ValueType) -> crate::Result<bool> {
+        Ok(self.dtype()?.is_compatible_with(other))
+    }
+
+    /// Returns the number of dimensions of this tensor.
+    pub fn rank(&self) -> crate::Result<usize> {
+        self.with_type_and_shape(|tensor_info_ptr| {
+            let mut num_dims = 0;
+            ortsys![unsafe GetDimensionsCount(tensor_info_ptr, &mut num_dims) -> crate::Error::GetDimensionsCount];
+            Ok(num_dims as _)
+        })
+    }
+
+    /// Returns the size of dimension `i` of this tensor; `-1` for a symbolic dimension.
+    pub fn dim(&self, i: usize) -> crate::Result<i64> {
+        self.with_type_and_shape(|tensor_info_ptr| {
+            let mut num_dims = 0;
+            ortsys![unsafe GetDimensionsCount(tensor_info_ptr, &mut num_dims) -> crate::Error::GetDimensionsCount];
+            let rank: usize = num_dims as _;
+            if i >= rank {
+                return Err(crate::Error::DimensionOutOfRange { index: i, rank });
+            }
+            // avoid allocating for tensors of reasonable rank
+            let mut dims_stack = [0i64; 8];
+            let mut dims_heap = Vec::new();
+            let dims: &mut [i64] = if rank <= dims_stack.len() {
+                &mut dims_stack[..rank]
+            } else {
+                dims_heap.resize(rank, 0);
+                &mut dims_heap
+            };
+            ortsys![unsafe GetDimensions(tensor_info_ptr, dims.as_mut_ptr(), num_dims) -> crate::Error::GetDimensions];
+            Ok(dims[i])
+        })
+    }
+
+    /// Returns this tensor's element type, dimensions & element count, queried from ONNX Runtime in one go; prefer this
+    /// over calling [`RustOwnerValue::dtype`], [`RustOwnerValue::rank`] and [`RustOwnerValue::dim`] separately when
+    /// several of them are needed.
+    pub fn type_and_shape(&self) -> crate::Result<TensorTypeAndShape> {
+        self.with_type_and_shape(|tensor_info_ptr| {
+            let mut type_sys = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
+            ortsys![unsafe GetTensorElementType(tensor_info_ptr, &mut type_sys) -> crate::Error::GetTensorElementType];
+            let mut num_dims = 0;
+            ortsys![unsafe GetDimensionsCount(tensor_info_ptr, &mut num_dims) -> crate::Error::GetDimensionsCount];
+            let mut dimensions: Vec<i64> = vec![0; num_dims as _];
+            ortsys![unsafe GetDimensions(tensor_info_ptr, dimensions.as_mut_ptr(), num_dims) -> crate::Error::GetDimensions];
+            let mut element_count = 0;
+            ortsys![unsafe GetTensorShapeElementCount(tensor_info_ptr, &mut element_count) -> crate::Error::GetTensorShapeElementCount];
+            Ok(TensorTypeAndShape {
+                element_type: type_sys.into(),
+                dimensions,
+                element_count: element_count as _,
+            })
+        })
+    }
+
+    fn with_type_and_shape<R>(&self, f: impl FnOnce(*const ort_sys::OrtTensorTypeAndShapeInfo) -> crate::Result<R>) -> crate::Result<R> {
+        let mut tensor_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
+        ortsys![unsafe GetTensorTypeAndShape(self.ptr, &mut tensor_info_ptr) -> crate::Error::GetTensorTypeAndShape; nonNull(tensor_info_ptr)];
+        let res = f(tensor_info_ptr);
+        ortsys![unsafe ReleaseTensorTypeAndShapeInfo(tensor_info_ptr)];
+        res
+    }
 }
 
 impl<Container, T> RustOwnerValue<Container>
@@ -43,36 +203,48 @@ impl<Container, T> RustOwnerValue<Container>
         Container: std::ops::Deref<Target=[T]>,
         T: IntoTensorElementType + Debug + Clone + 'static,
 {
+    /// Creates a tensor of the given shape backed by `data`, which must hold at least as many elements as `shape`
+    /// describes; otherwise [`Error::NotDenselyPacked`](crate::Error::NotDenselyPacked) is returned.
+    ///
+    /// # Permissive vs. exact lengths
+    ///
+    /// `new` (like [`RustOwnerValue::new_mut`] and their `_with_allocator` variants) accepts data *longer* than the
+    /// shape requires and silently ignores the trailing elements, so one large buffer can back a tensor that only views
+    /// its prefix. The flip side is that a buffer reshaped incorrectly is not caught: the tensor just sees the wrong
+    /// elements. Use [`RustOwnerValue::new_exact`] wherever the data is meant to match the shape exactly.
     pub fn new(shape: &[i64], data: Container) -> crate::Result<Self, RunError> {
-        let len = shape.iter().fold(1, |a, b| a * b);
-        if data.len() < len as usize {
-            return Err(RunError::Msg(format!("data len should be >= target len: [{} >= {}]?", data.len(), len)));
-        }
-        let shape_ptr: *const i64 = shape.as_ptr();
-        let shape_len = shape.len();
-        let memory_info = MemoryInfo::new_cpu(AllocatorType::Arena, MemType::Default)?;
-        let tensor_values_ptr: *mut std::ffi::c_void = data.as_ptr() as *mut std::ffi::c_void;
-        assert_non_null_pointer(tensor_values_ptr, "TensorValues")?;
-        let mut value_ptr: *mut ort_sys::OrtValue = std::ptr::null_mut();
-        ortsys![
-            unsafe CreateTensorWithDataAsOrtValue(
-                memory_info.ptr,
-                tensor_values_ptr,
-                (data.len() * std::mem::size_of::<T>()) as _,
-                shape_ptr,
-                shape_len as _,
-                T::into_tensor_element_type().into(),
-                &mut value_ptr
-            ) -> crate::Error::CreateTensorWithData;
-            nonNull(value_ptr)
-        ];
-        let mut is_tensor = 0;
-        ortsys![unsafe IsTensor(value_ptr, &mut is_tensor) -> crate::Error::FailedTensorCheck];
-        assert_eq!(is_tensor, 1);
+        Self::new_with_allocator(shape, data, AllocatorType::Arena, MemType::Default)
+    }
+
+    /// Creates a tensor of the given shape backed by `data`, requiring `data.len()` to equal the number of elements
+    /// `shape` describes; otherwise [`Error::NotDenselyPacked`](crate::Error::NotDenselyPacked) is returned.
+    ///
+    /// Unlike [`RustOwnerValue::new`], trailing data is rejected rather than ignored; see
+    /// [Permissive vs. exact lengths](RustOwnerValue::new#permissive-vs-exact-lengths).
+    pub fn new_exact(shape: &[i64], data: Container) -> crate::Result<Self, RunError> {
+        check_densely_packed(shape, data.len())?;
+        Self::from_data(shape, data, AllocatorType::Arena, MemType::Default)
+    }
+
+    /// Like [`RustOwnerValue::new`], but describes `data` with a CPU [`MemoryInfo`] of the given allocator & memory type,
+    /// e.g. [`AllocatorType::Device`] for short-lived tensors that shouldn't be retained by the arena.
+    pub fn new_with_allocator(shape: &[i64], data: Container, allocator: AllocatorType, memory_type: MemType) -> crate::Result<Self, RunError> {
+        check_data_len(shape, data.len())?;
+        Self::from_data(shape, data, allocator, memory_type)
+    }
+
+    /// Creates the tensor once the caller has checked `data`'s length against `shape`.
+    fn from_data(shape: &[i64], data: Container, allocator: AllocatorType, memory_type: MemType) -> crate::Result<Self, RunError> {
+        check_element_size::<T>()?;
+        let memory_info = MemoryInfo::new_cpu_shared(allocator, memory_type).map_err(RunError::context("creating CPU memory info"))?;
+        let element_type = T::into_tensor_element_type();
+        let value_ptr = create_tensor_with_data(&memory_info, data.as_ptr() as *mut std::ffi::c_void, data.len() * std::mem::size_of::<T>(), shape, element_type.into())
+            .map_err(RunError::context(format!("creating {element_type:?} tensor of shape {shape:?}")))?;
         Ok(Self {
             ptr: value_ptr,
             owner: data,
             _memory_info: memory_info,
+            release_value: true,
         })
     }
     #[inline]
@@ -91,36 +263,25 @@ impl<Container, T> RustOwnerValue<Container>
         Container: std::ops::DerefMut<Target=[T]>,
         T: IntoTensorElementType + Debug + Clone + 'static,
 {
-    pub fn new_mut(shape: &[i64], mut data: Container) -> crate::Result<Self, RunError> {
-        let len = shape.iter().fold(1, |a, b| a * b);
-        if data.len() < len as usize {
-            return Err(RunError::Msg(format!("data len should be >= target len: [{} >= {}]?", data.len(), len)));
-        }
-        let shape_ptr: *const i64 = shape.as_ptr();
-        let shape_len = shape.len();
-        let memory_info = MemoryInfo::new_cpu(AllocatorType::Arena, MemType::Default)?;
-        let tensor_values_ptr: *mut std::ffi::c_void = data.as_mut_ptr() as *mut std::ffi::c_void;
-        assert_non_null_pointer(tensor_values_ptr, "TensorValues")?;
-        let mut value_ptr: *mut ort_sys::OrtValue = std::ptr::null_mut();
-        ortsys![
-            unsafe CreateTensorWithDataAsOrtValue(
-                memory_info.ptr,
-                tensor_values_ptr,
-                (data.len() * std::mem::size_of::<T>()) as _,
-                shape_ptr,
-                shape_len as _,
-                T::into_tensor_element_type().into(),
-                &mut value_ptr
-            ) -> crate::Error::CreateTensorWithData;
-            nonNull(value_ptr)
-        ];
-        let mut is_tensor = 0;
-        ortsys![unsafe IsTensor(value_ptr, &mut is_tensor) -> crate::Error::FailedTensorCheck];
-        assert_eq!(is_tensor, 1);
+    /// Like [`RustOwnerValue::new`], for mutable containers, i.e. output buffers.
+    pub fn new_mut(shape: &[i64], data: Container) -> crate::Result<Self, RunError> {
+        Self::new_mut_with_allocator(shape, data, AllocatorType::Arena, MemType::Default)
+    }
+
+    /// Like [`RustOwnerValue::new_mut`], but describes `data` with a CPU [`MemoryInfo`] of the given allocator & memory
+    /// type.
+    pub fn new_mut_with_allocator(shape: &[i64], mut data: Container, allocator: AllocatorType, memory_type: MemType) -> crate::Result<Self, RunError> {
+        check_data_len(shape, data.len())?;
+        check_element_size::<T>()?;
+        let memory_info = MemoryInfo::new_cpu_shared(allocator, memory_type).map_err(RunError::context("creating CPU memory info"))?;
+        let element_type = T::into_tensor_element_type();
+        let value_ptr = create_tensor_with_data(&memory_info, data.as_mut_ptr() as *mut std::ffi::c_void, data.len() * std::mem::size_of::<T>(), shape, element_type.into())
+            .map_err(RunError::context(format!("creating {element_type:?} tensor of shape {shape:?}")))?;
         Ok(Self {
             ptr: value_ptr,
             owner: data,
             _memory_info: memory_info,
+            release_value: true,
         })
     }
     #[inline]
@@ -134,174 +295,469 @@ impl<Container, T> RustOwnerValue<Container>
     }
 }
 
-pub fn get_type_size(type_: ONNXTensorElementDataType) -> Result<usize, &'static str> {
-    let size = match type_ {
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED => { 0 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT => { 4 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT8 => { 1 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT8 => { 1 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT16 => { 2 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT16 => { 2 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT32 => { 4 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT64 => { 8 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING => { return Err("unsupported ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING"); }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BOOL => { 1 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT16 => { 2 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_DOUBLE => { 8 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT32 => { 4 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT64 => { 8 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX64 => { 8 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX128 => { 16 }
-        ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BFLOAT16 => { 2 }
+/// Derefs to the data backing the tensor, so slice methods can be used directly. For values created with
+/// [`RustOwnerValue::with_any_type`], this is the raw bytes of the tensor; see [`RustOwnerValue::reinterpret`] to view
+/// them as their actual element type.
+impl<Container, T> std::ops::Deref for RustOwnerValue<Container>
+    where
+        Container: std::ops::Deref<Target=[T]>,
+{
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        &self.owner
+    }
+}
+
+impl<Container, T> std::ops::DerefMut for RustOwnerValue<Container>
+    where
+        Container: std::ops::DerefMut<Target=[T]>,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.owner
+    }
+}
+
+/// Returns the element type of the tensor `value_ptr`.
+fn tensor_element_type(value_ptr: *const ort_sys::OrtValue) -> crate::Result<TensorElementType> {
+    let mut tensor_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
+    ortsys![unsafe GetTensorTypeAndShape(value_ptr, &mut tensor_info_ptr) -> crate::Error::GetTensorTypeAndShape; nonNull(tensor_info_ptr)];
+    let mut type_sys = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
+    let status = ortsys![unsafe GetTensorElementType(tensor_info_ptr, &mut type_sys)];
+    ortsys![unsafe ReleaseTensorTypeAndShapeInfo(tensor_info_ptr)];
+    crate::error::status_to_result(status).map_err(crate::Error::GetTensorElementType)?;
+    Ok(type_sys.into())
+}
+
+/// Returns the data pointer of the tensor `value_ptr` after checking its element type is `T`.
+pub(crate) fn tensor_data_ptr<T: IntoTensorElementType>(value_ptr: *mut ort_sys::OrtValue) -> crate::Result<*mut T> {
+    let mut tensor_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
+    ortsys![unsafe GetTensorTypeAndShape(value_ptr, &mut tensor_info_ptr) -> crate::Error::GetTensorTypeAndShape; nonNull(tensor_info_ptr)];
+    let mut type_sys = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
+    let mut element_count = 0;
+    let status = ortsys![unsafe GetTensorElementType(tensor_info_ptr, &mut type_sys)];
+    let count_status = ortsys![unsafe GetTensorShapeElementCount(tensor_info_ptr, &mut element_count)];
+    ortsys![unsafe ReleaseTensorTypeAndShapeInfo(tensor_info_ptr)];
+    crate::error::status_to_result(status).map_err(crate::Error::GetTensorElementType)?;
+    crate::error::status_to_result(count_status).map_err(crate::Error::GetTensorShapeElementCount)?;
+
+    let actual: TensorElementType = type_sys.into();
+    let requested = T::into_tensor_element_type();
+    if actual != requested {
+        return Err(crate::Error::DataTypeMismatch { actual, requested });
+    }
+    unchecked_tensor_data_ptr(value_ptr, element_count as _)
+}
+
+/// Returns the data pointer of the tensor `value_ptr`, which holds `element_count` elements of type `T`, without
+/// checking its element type.
+///
+/// ONNX Runtime may not allocate any memory for a zero-element tensor, leaving its data pointer null, so a dangling,
+/// well-aligned pointer is returned for those instead; callers can always build a (possibly empty) slice from it.
+pub(crate) fn unchecked_tensor_data_ptr<T>(value_ptr: *mut ort_sys::OrtValue, element_count: usize) -> crate::Result<*mut T> {
+    if element_count == 0 {
+        return Ok(std::ptr::NonNull::dangling().as_ptr());
+    }
+    let mut data_ptr: *mut std::ffi::c_void = std::ptr::null_mut();
+    ortsys![unsafe GetTensorMutableData(value_ptr, &mut data_ptr) -> crate::Error::GetTensorMutableData; nonNull(data_ptr)];
+    Ok(data_ptr as *mut T)
+}
+
+/// Returns the number of elements in a tensor of the given `shape`, or an error if a dimension is negative (i.e.
+/// symbolic) or the count overflows.
+fn shape_element_count(shape: &[i64]) -> crate::Result<usize> {
+    shape
+        .iter()
+        .try_fold(1usize, |count, &dim| usize::try_from(dim).ok().and_then(|dim| count.checked_mul(dim)))
+        .ok_or_else(|| crate::Error::InvalidShape(shape.to_vec()))
+}
+
+/// Checks that a container of `len` elements holds at least as many elements as `shape` describes, as required by the
+/// typed [`RustOwnerValue`] constructors; any excess is ignored.
+fn check_data_len(shape: &[i64], len: usize) -> crate::Result<()> {
+    let expected = shape_element_count(shape)?;
+    if len < expected {
+        return Err(crate::Error::NotDenselyPacked { shape: shape.to_vec(), expected, actual: len });
+    }
+    Ok(())
+}
+
+/// Checks that a container of `len` elements is densely packed for `shape`, i.e. that it holds exactly as many
+/// elements as the shape describes.
+///
+/// ONNX Runtime reads tensor data as contiguous & row-major, so data with padded rows or other strides would be
+/// silently misread. [`RustOwnerValue::new_exact`] performs this check; [`RustOwnerValue::new`], [`RustOwnerValue::new_mut`]
+/// and the byte-based [`RustOwnerValue::with_any_type`] constructors accept buffers longer than the shape requires and
+/// ignore the excess.
+pub fn check_densely_packed(shape: &[i64], len: usize) -> crate::Result<()> {
+    let expected = shape_element_count(shape)?;
+    if len != expected {
+        return Err(crate::Error::NotDenselyPacked { shape: shape.to_vec(), expected, actual: len });
+    }
+    Ok(())
+}
+
+/// Checks that `T`'s [`IntoTensorElementType`] impl maps it to an element type of the same size, since the byte length
+/// passed to ONNX Runtime is derived from `size_of::<T>()`.
+fn check_element_size<T: IntoTensorElementType>() -> crate::Result<(), RunError> {
+    let element_type = T::into_tensor_element_type();
+    let expected = get_type_size(element_type.into())?;
+    if std::mem::size_of::<T>() != expected {
+        return Err(RunError::Msg(format!(
+            "`{}` is {} bytes, but its element type {element_type:?} is {expected} bytes",
+            std::any::type_name::<T>(),
+            std::mem::size_of::<T>()
+        )));
+    }
+    Ok(())
+}
+
+/// Creates a tensor over `data_len_bytes` bytes at `tensor_values_ptr`.
+///
+/// Zero-element tensors, i.e. of shape `[0, N]`, are allowed: their data pointer is never read, so an empty buffer's
+/// (possibly null or dangling) pointer is replaced with a well-aligned non-null one, which is also what ONNX Runtime
+/// hands back from `GetTensorMutableData`.
+fn create_tensor_with_data(
+    memory_info: &MemoryInfo,
+    tensor_values_ptr: *mut std::ffi::c_void,
+    data_len_bytes: usize,
+    shape: &[i64],
+    element_type: ONNXTensorElementDataType,
+) -> crate::Result<*mut ort_sys::OrtValue> {
+    let tensor_values_ptr = if data_len_bytes == 0 {
+        // aligned for every element type
+        std::ptr::NonNull::<u128>::dangling().as_ptr() as *mut std::ffi::c_void
+    } else {
+        assert_non_null_pointer(tensor_values_ptr, "TensorValues")?;
+        tensor_values_ptr
     };
-    Ok(size)
+    let mut value_ptr: *mut ort_sys::OrtValue = std::ptr::null_mut();
+    ortsys![
+        unsafe CreateTensorWithDataAsOrtValue(
+            memory_info.ptr,
+            tensor_values_ptr,
+            data_len_bytes as _,
+            shape.as_ptr(),
+            shape.len() as _,
+            element_type,
+            &mut value_ptr
+        ) -> crate::Error::CreateTensorWithData;
+        nonNull(value_ptr)
+    ];
+    let mut is_tensor = 0;
+    ortsys![unsafe IsTensor(value_ptr, &mut is_tensor) -> crate::Error::FailedTensorCheck];
+    assert_eq!(is_tensor, 1);
+    Ok(value_ptr)
+}
+
+/// Every tensor element type, with its size in bytes (`None` if elements aren't fixed-size). This is the single source
+/// of truth for [`get_type_size`] and [`convert_to_onnx_el_type`], so new types only need to be added here.
+const ELEMENT_TYPES: [(ONNXTensorElementDataType, Option<usize>); 17] = [
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED, Some(0)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT, Some(4)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT8, Some(1)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT8, Some(1)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT16, Some(2)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT16, Some(2)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT32, Some(4)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT64, Some(8)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING, None),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BOOL, Some(1)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT16, Some(2)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_DOUBLE, Some(8)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT32, Some(4)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT64, Some(8)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX64, Some(8)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX128, Some(16)),
+    (ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BFLOAT16, Some(2)),
+];
+
+/// Returns the size in bytes of a single element of `type_`.
+///
+/// Returns [`Error::UnsupportedElementType`](crate::Error::UnsupportedElementType) for types whose elements have no
+/// fixed size, i.e. strings.
+pub fn get_type_size(type_: ONNXTensorElementDataType) -> crate::Result<usize> {
+    ELEMENT_TYPES
+        .iter()
+        .find(|(ty, _)| *ty == type_)
+        .and_then(|(_, size)| *size)
+        .ok_or_else(|| crate::Error::UnsupportedElementType(type_.into()))
 }
 
 pub fn convert_to_onnx_el_type(i: i32) -> Result<ONNXTensorElementDataType, String> {
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT8: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT8 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_INT8: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT8 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT16: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT16 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_INT16: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT16 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_INT32: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT32 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_INT64: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT64 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_BOOL: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BOOL as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT16: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT16 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_DOUBLE: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_DOUBLE as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT32: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT32 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT64: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT64 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX64: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX64 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX128: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX128 as i32;
-    const ONNX_TENSOR_ELEMENT_DATA_TYPE_BFLOAT16: i32 = ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BFLOAT16 as i32;
-    let t = match i {
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT8 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT8
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_INT8 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT8
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT16 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT16
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_INT16 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT16
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_INT32 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT32
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_INT64 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT64
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_BOOL => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BOOL
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT16 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT16
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_DOUBLE => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_DOUBLE
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT32 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT32
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT64 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT64
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX64 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX64
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX128 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX128
-        }
-        ONNX_TENSOR_ELEMENT_DATA_TYPE_BFLOAT16 => {
-            ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BFLOAT16
-        }
-        _ => { return Err(format!("unknown type: {i}")); }
-    };
-    Ok(t)
+    ELEMENT_TYPES
+        .iter()
+        .map(|(ty, _)| *ty)
+        .find(|ty| *ty as i32 == i)
+        .ok_or_else(|| format!("unknown type: {i}"))
 }
 
-impl<'a> RustOwnerValue<&'a [u8]> {
-    /// for shared memory
-    pub fn with_any_type(shape: &[i64], data: &'a [u8], type_: ONNXTensorElementDataType) -> crate::Result<Self, RunError> {
-        let size = get_type_size(type_).unwrap();
-        let len = shape.iter().fold(1, |a, b| a * b) as usize * size;
-        if data.len() < len {
-            return Err(RunError::Msg(format!("data len should be >= target len: [{} >= {}]?", data.len(), len)));
+impl<T> RustOwnerValue<Vec<T>>
+    where
+        T: IntoTensorElementType + Debug + Clone + 'static,
+{
+    /// Creates a tensor of the given shape with every element set to `value`. Shapes with a negative dimension or too
+    /// many elements return [`Error::InvalidShape`](crate::Error::InvalidShape).
+    pub fn full(shape: &[i64], value: T) -> crate::Result<Self, RunError> {
+        let len = shape_element_count(shape)?;
+        Self::new(shape, vec![value; len])
+    }
+
+    /// Creates a tensor of the given shape filled with [`T::default()`](Default::default).
+    ///
+    /// For every element type supported by `ort`, the default value is zero and is represented by all bits being zero:
+    /// `0` for integers, `false` for `bool`, and positive zero (`+0.0`) for `f32`, `f64`, `f16` (`0x0000`) and `bf16`
+    /// (`0x0000`). To fill with another bit pattern, i.e. negative zero, use [`RustOwnerValue::full`].
+    pub fn zeros(shape: &[i64]) -> crate::Result<Self, RunError>
+        where
+            T: Default,
+    {
+        Self::full(shape, T::default())
+    }
+}
+
+impl RustOwnerValue<Vec<f32>> {
+    /// Creates a `[num_classes]` tensor that is `1.0` at `index` and `0.0` everywhere else, i.e. a class label for a
+    /// conditional model. For a tensor filled with a single value, see [`RustOwnerValue::full`].
+    pub fn one_hot(num_classes: i64, index: i64) -> crate::Result<Self, RunError> {
+        if !(0..num_classes).contains(&index) {
+            return Err(RunError::Msg(format!("one-hot index {index} is out of range for {num_classes} classes")));
         }
-        let shape_ptr: *const i64 = shape.as_ptr();
-        let shape_len = shape.len();
-        let memory_info = MemoryInfo::new_cpu(AllocatorType::Arena, MemType::Default)?;
-        let tensor_values_ptr: *mut std::ffi::c_void = data.as_ptr() as *mut std::ffi::c_void;
-        assert_non_null_pointer(tensor_values_ptr, "TensorValues")?;
+        let mut data = vec![0.0; num_classes as usize];
+        data[index as usize] = 1.0;
+        Self::new(&[num_classes], data)
+    }
+}
+
+/// The data of a tensor allocated by ONNX Runtime, created with [`RustOwnerValue::new_allocated`]. It owns the tensor's
+/// `OrtValue`, so the data stays valid after [`RustOwnerValue::into_container`], and borrows the allocator, which
+/// ONNX Runtime uses to free the data.
+pub struct AllocatedBuffer<'a, T> {
+    value_ptr: *mut ort_sys::OrtValue,
+    data: *mut T,
+    len: usize,
+    _allocator: std::marker::PhantomData<&'a Allocator>,
+}
+
+impl<T> std::ops::Deref for AllocatedBuffer<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.data, self.len) }
+    }
+}
+
+impl<T> std::ops::DerefMut for AllocatedBuffer<'_, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.data, self.len) }
+    }
+}
+
+impl<T> Drop for AllocatedBuffer<'_, T> {
+    fn drop(&mut self) {
+        ortsys![unsafe ReleaseValue(self.value_ptr)];
+    }
+}
+
+impl<'a, T> RustOwnerValue<AllocatedBuffer<'a, T>>
+    where
+        T: IntoTensorElementType + Debug + Clone + 'static,
+{
+    /// Creates a zero-filled tensor whose data is allocated by `allocator` (via `CreateTensorAsOrtValue`) instead of
+    /// by Rust, i.e. to place an input in a session's arena; pass [`Session::allocator`](crate::Session::allocator) or
+    /// [`Allocator::default`]. Fill it through [`RustOwnerValue::as_mut_slice`].
+    pub fn new_allocated(shape: &[i64], allocator: &'a Allocator) -> crate::Result<Self, RunError> {
+        let len = shape_element_count(shape)?;
+        check_element_size::<T>()?;
+        let element_type = T::into_tensor_element_type();
         let mut value_ptr: *mut ort_sys::OrtValue = std::ptr::null_mut();
         ortsys![
-            unsafe CreateTensorWithDataAsOrtValue(
-                memory_info.ptr,
-                tensor_values_ptr,
-                data.len() as _,
-                shape_ptr,
-                shape_len as _,
-                type_,
-                &mut value_ptr
-            ) -> crate::Error::CreateTensorWithData;
+            unsafe CreateTensorAsOrtValue(allocator.ptr, shape.as_ptr(), shape.len() as _, element_type.into(), &mut value_ptr)
+                -> crate::Error::CreateTensor;
             nonNull(value_ptr)
         ];
-        let mut is_tensor = 0;
-        ortsys![unsafe IsTensor(value_ptr, &mut is_tensor) -> crate::Error::FailedTensorCheck];
-        assert_eq!(is_tensor, 1);
+        let buffer = AllocatedBuffer {
+            value_ptr,
+            data: tensor_data_ptr(value_ptr)?,
+            len,
+            _allocator: std::marker::PhantomData,
+        };
+        // the allocation is uninitialized; all bits zero is a valid value of every element type
+        unsafe { std::ptr::write_bytes(buffer.data, 0, len) };
         Ok(Self {
             ptr: value_ptr,
-            owner: data,
-            _memory_info: memory_info,
+            owner: buffer,
+            _memory_info: MemoryInfo::from_value(value_ptr)?,
+            release_value: false,
         })
     }
 }
 
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+impl RustOwnerValue<Vec<f32>> {
+    /// Creates a `[1, 3, H, W]` (NCHW) tensor from an RGB image, normalizing each channel `c` of each pixel `p` as
+    /// `(p / 255 - mean[c]) / std[c]`.
+    ///
+    /// `mean` and `std` are on the `[0, 1]` scale, i.e. `[0.485, 0.456, 0.406]` and `[0.229, 0.224, 0.225]` for models
+    /// trained on ImageNet.
+    pub fn from_image(img: &image::RgbImage, mean: [f32; 3], std: [f32; 3]) -> crate::Result<Self, RunError> {
+        let (width, height) = img.dimensions();
+        let shape = [1, 3, height as i64, width as i64];
+        let plane = width as usize * height as usize;
+        let len = plane.checked_mul(3).ok_or_else(|| crate::Error::InvalidShape(shape.to_vec()))?;
+        let mut data = vec![0.0f32; len];
+        for (i, pixel) in img.pixels().enumerate() {
+            for c in 0..3 {
+                data[c * plane + i] = (pixel[c] as f32 / 255.0 - mean[c]) / std[c];
+            }
+        }
+        Self::new(&shape, data)
+    }
+}
+
+impl<Container> RustOwnerValue<Container>
+    where
+        Container: std::ops::Deref<Target=[u8]>,
+{
+    /// Returns the raw bytes of a tensor created with [`RustOwnerValue::with_any_type`] or
+    /// [`RustOwnerValue::with_any_type_mut`], whose element type is only known at runtime.
+    ///
+    /// Only the bytes of the tensor's elements are returned; if the buffer is longer than the shape requires, the
+    /// excess is left out.
+    pub fn typed_bytes(&self) -> crate::Result<&[u8]> {
+        Ok(self.tensor_bytes()?.1)
+    }
+
+    /// Reinterprets the raw bytes of this tensor as a slice of `T`, after checking that the tensor's element type is
+    /// `T` and that the data is suitably aligned for `T`. Like [`RustOwnerValue::typed_bytes`], any excess bytes in the
+    /// buffer are left out.
+    pub fn reinterpret<T: IntoTensorElementType>(&self) -> crate::Result<&[T]> {
+        let (actual, bytes) = self.tensor_bytes()?;
+        let requested = T::into_tensor_element_type();
+        if actual != requested {
+            return Err(crate::Error::DataTypeMismatch { actual, requested });
+        }
+        // every bit pattern is a valid value of the numeric element types, but not of `bool`
+        if requested == TensorElementType::Bool && bytes.iter().any(|&b| b > 1) {
+            return Err(crate::Error::InvalidBoolTensorData);
+        }
+        // SAFETY: the element type and, for `bool`, the contents were checked above.
+        let (prefix, data, _) = unsafe { bytes.align_to::<T>() };
+        if !prefix.is_empty() {
+            return Err(crate::Error::MisalignedTensorData);
+        }
+        Ok(data)
+    }
+
+    /// Returns the tensor's element type along with the bytes of its elements, i.e. the buffer without any excess.
+    fn tensor_bytes(&self) -> crate::Result<(TensorElementType, &[u8])> {
+        let TensorTypeAndShape { element_type, element_count, .. } = self.type_and_shape()?;
+        let len = element_count * get_type_size(element_type.into())?;
+        Ok((element_type, &self.owner[..len]))
+    }
+}
+
+impl<'a> RustOwnerValue<&'a [u8]> {
+    /// for shared memory; the bytes of `data` are interpreted as elements of `type_`. Element types without a fixed
+    /// size, such as strings, return [`Error::UnsupportedElementType`](crate::Error::UnsupportedElementType).
+    pub fn with_any_type(shape: &[i64], data: &'a [u8], type_: TensorElementType) -> crate::Result<Self, RunError> {
+        Self::with_any_type_and_allocator(shape, data, type_, AllocatorType::Arena, MemType::Default)
+    }
+
+    /// Like [`RustOwnerValue::with_any_type`], but with the given allocator & memory type.
+    pub fn with_any_type_and_allocator(
+        shape: &[i64],
+        data: &'a [u8],
+        type_: TensorElementType,
+        allocator: AllocatorType,
+        memory_type: MemType,
+    ) -> crate::Result<Self, RunError> {
+        new_any_type(shape, data, type_, allocator, memory_type)
+    }
+}
+
+#[cfg(feature = "memmap2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "memmap2")))]
+impl RustOwnerValue<memmap2::Mmap> {
+    /// Creates a tensor from the raw bytes of a memory-mapped file without copying them, e.g. for a large embedding
+    /// table that is reused across runs. Like [`RustOwnerValue::with_any_type`], the mapping may be longer than the
+    /// tensor; only the start of it is used.
+    ///
+    /// The value owns the mapping, so the file stays mapped for as long as the value is alive. For `Uint8` tensors,
+    /// [`RustOwnerValue::new`] also accepts an [`Mmap`](memmap2::Mmap), since it derefs to `[u8]`.
+    ///
+    /// ```no_run
+    /// # use ort::{RustOwnerValue, TensorElementType};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = std::fs::File::open("embeddings.bin")?;
+    /// // SAFETY: the file must not be modified while it is mapped
+    /// let mmap = unsafe { memmap2::Mmap::map(&file)? };
+    /// let embeddings = RustOwnerValue::from_mmap(&[50257, 768], mmap, TensorElementType::Float32)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_mmap(shape: &[i64], mmap: memmap2::Mmap, element_type: TensorElementType) -> crate::Result<Self, RunError> {
+        new_any_type(shape, mmap, element_type, AllocatorType::Arena, MemType::Default)
+    }
+}
+
+/// Creates a tensor of `type_` backed by the bytes of `data`, which ONNX Runtime only reads.
+fn new_any_type<Container>(
+    shape: &[i64],
+    data: Container,
+    type_: TensorElementType,
+    allocator: AllocatorType,
+    memory_type: MemType,
+) -> crate::Result<RustOwnerValue<Container>, RunError>
+    where
+        Container: std::ops::Deref<Target=[u8]>,
+{
+    let size = get_type_size(type_.into())?;
+    let len = shape_element_count(shape)?.checked_mul(size).ok_or_else(|| crate::Error::InvalidShape(shape.to_vec()))?;
+    if data.len() < len {
+        return Err(RunError::Msg(format!("data len should be >= target len: [{} >= {}]?", data.len(), len)));
+    }
+    let memory_info = MemoryInfo::new_cpu_shared(allocator, memory_type).map_err(RunError::context("creating CPU memory info"))?;
+    let value_ptr = create_tensor_with_data(&memory_info, data.as_ptr() as *mut std::ffi::c_void, data.len(), shape, type_.into())
+        .map_err(RunError::context(format!("creating {type_:?} tensor of shape {shape:?}")))?;
+    Ok(RustOwnerValue {
+        ptr: value_ptr,
+        owner: data,
+        _memory_info: memory_info,
+        release_value: true,
+    })
+}
+
 impl<'a> RustOwnerValue<&'a mut [u8]> {
-    /// for shared memory
-    pub fn with_any_type_mut(shape: &[i64], data: &'a mut [u8], type_: ONNXTensorElementDataType) -> crate::Result<Self, RunError> {
-        let size = get_type_size(type_).unwrap();
-        let len = shape.iter().fold(1, |a, b| a * b) as usize * size;
+    /// Like [`RustOwnerValue::with_any_type`], for mutable buffers.
+    pub fn with_any_type_mut(shape: &[i64], data: &'a mut [u8], type_: TensorElementType) -> crate::Result<Self, RunError> {
+        Self::with_any_type_mut_and_allocator(shape, data, type_, AllocatorType::Arena, MemType::Default)
+    }
+
+    /// Like [`RustOwnerValue::with_any_type_mut`], but with the given allocator & memory type.
+    pub fn with_any_type_mut_and_allocator(
+        shape: &[i64],
+        data: &'a mut [u8],
+        type_: TensorElementType,
+        allocator: AllocatorType,
+        memory_type: MemType,
+    ) -> crate::Result<Self, RunError> {
+        let size = get_type_size(type_.into())?;
+        let len = shape_element_count(shape)?.checked_mul(size).ok_or_else(|| crate::Error::InvalidShape(shape.to_vec()))?;
         if data.len() < len {
             return Err(RunError::Msg(format!("data len should be >= target len: [{} >= {}]?", data.len(), len)));
         }
-        let shape_ptr: *const i64 = shape.as_ptr();
-        let shape_len = shape.len();
-        let memory_info = MemoryInfo::new_cpu(AllocatorType::Arena, MemType::Default)?;
-        let tensor_values_ptr: *mut std::ffi::c_void = data.as_mut_ptr() as *mut std::ffi::c_void;
-        assert_non_null_pointer(tensor_values_ptr, "TensorValues")?;
-        let mut value_ptr: *mut ort_sys::OrtValue = std::ptr::null_mut();
-        ortsys![
-            unsafe CreateTensorWithDataAsOrtValue(
-                memory_info.ptr,
-                tensor_values_ptr,
-                data.len() as _,
-                shape_ptr,
-                shape_len as _,
-                type_,
-                &mut value_ptr
-            ) -> crate::Error::CreateTensorWithData;
-            nonNull(value_ptr)
-        ];
-        let mut is_tensor = 0;
-        ortsys![unsafe IsTensor(value_ptr, &mut is_tensor) -> crate::Error::FailedTensorCheck];
-        assert_eq!(is_tensor, 1);
+        let memory_info = MemoryInfo::new_cpu_shared(allocator, memory_type).map_err(RunError::context("creating CPU memory info"))?;
+        let value_ptr = create_tensor_with_data(&memory_info, data.as_mut_ptr() as *mut std::ffi::c_void, data.len(), shape, type_.into())
+            .map_err(RunError::context(format!("creating {type_:?} tensor of shape {shape:?}")))?;
         Ok(Self {
             ptr: value_ptr,
             owner: data,
             _memory_info: memory_info,
+            release_value: true,
         })
     }
 }
@@ -338,6 +794,63 @@ impl<T, Container> Names<Container>
     }
 }
 
+impl Names<Vec<CString>> {
+    /// Creates an empty [`Names`] with space for at least `capacity` names.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            ptrs: Vec::with_capacity(capacity),
+            names: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Appends a name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` contains a nul byte.
+    pub fn push(&mut self, name: impl AsRef<str>) {
+        let name = CString::new(name.as_ref()).unwrap();
+        // the pointer stays valid when the `CString` is moved, since its contents are heap-allocated
+        self.ptrs.push(name.as_ptr());
+        self.names.push(name);
+    }
+
+    /// Creates a [`Names`] holding only `name`, for single-input or single-output models.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` contains a nul byte.
+    pub fn single(name: impl AsRef<str>) -> Self {
+        let mut names = Self::with_capacity(1);
+        names.push(name);
+        names
+    }
+}
+
+impl From<&str> for Names<Vec<CString>> {
+    fn from(value: &str) -> Self {
+        Self::single(value)
+    }
+}
+
+impl From<String> for Names<Vec<CString>> {
+    fn from(value: String) -> Self {
+        Self::single(value)
+    }
+}
+
+impl<T: AsRef<str>> FromIterator<T> for Names<Vec<CString>> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut names = Self::with_capacity(iter.size_hint().0);
+        for name in iter {
+            names.push(name);
+        }
+        names
+    }
+}
+
 impl<T: AsRef<str>> From<Vec<T>> for Names<Vec<CString>> {
     fn from(value: Vec<T>) -> Self {
         let mut ptrs = Vec::with_capacity(value.len());
@@ -398,6 +911,21 @@ impl<Container> Values<Container> {
     pub fn new(values_: Vec<RustOwnerValue<Container>>) -> Self {
         Self::from(values_)
     }
+
+    /// Creates an empty [`Values`] with space for at least `capacity` values.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            ptrs: Vec::with_capacity(capacity),
+            values: Vec::with_capacity(capacity),
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: RustOwnerValue<Container>) {
+        self.ptrs.push(value.ptr);
+        self.values.push(value);
+    }
 }
 
 impl<T, Container> std::ops::Index<usize> for Values<Container>
@@ -437,10 +965,21 @@ impl<T, Container> Values<Container>
     pub fn as_ptr(&self) -> *const *const ort_sys::OrtValue {
         self.ptrs.as_ptr() as _
     }
+    /// The value pointers, as passed to ONNX Runtime for inputs.
+    #[inline]
+    fn input_ptrs(&self) -> &[*const ort_sys::OrtValue] {
+        // SAFETY: `*mut` & `*const` pointers have the same layout
+        unsafe { std::slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
     #[inline]
     pub fn as_slice(&self) -> &[RustOwnerValue<Container>] {
         self.values.as_slice()
     }
+    /// Returns the value at `index`, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&RustOwnerValue<Container>> {
+        self.values.get(index)
+    }
 }
 
 impl<T, Container> Values<Container>
@@ -455,26 +994,147 @@ impl<T, Container> Values<Container>
     pub fn as_mut_slice(&mut self) -> &mut [RustOwnerValue<Container>] {
         self.values.as_mut_slice()
     }
+    /// Returns the value at `index` mutably, or `None` if out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut RustOwnerValue<Container>> {
+        self.values.get_mut(index)
+    }
 }
 
 impl<Container> From<Vec<RustOwnerValue<Container>>> for Values<Container> {
     #[inline]
     fn from(values_: Vec<RustOwnerValue<Container>>) -> Self {
-        let mut values = Vec::with_capacity(values_.len());
-        let mut ptrs = Vec::with_capacity(values.len());
-        for value in values_ {
-            let ptr = value.ptr;
-            ptrs.push(ptr);
+        values_.into_iter().collect()
+    }
+}
+
+impl<Container> FromIterator<RustOwnerValue<Container>> for Values<Container> {
+    fn from_iter<I: IntoIterator<Item=RustOwnerValue<Container>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut values = Self::with_capacity(iter.size_hint().0);
+        for value in iter {
             values.push(value);
         }
-        Self {
-            ptrs,
-            values,
-        }
+        values
     }
 }
 
 impl super::Session {
+    /// Prepares a [`PreparedRun`] with the given input & output names, for running the session repeatedly without
+    /// re-deriving the names.
+    pub fn prepare_run<SI, SO, CNamesIn, CNamesOut>(&self, input_names: Names<CNamesIn>, output_names: Names<CNamesOut>) -> PreparedRun<'_, CNamesIn, CNamesOut>
+        where
+            CNamesIn: std::ops::Deref<Target=[SI]>,
+            CNamesOut: std::ops::Deref<Target=[SO]>,
+            SI: AsRef<std::ffi::CStr>,
+            SO: AsRef<std::ffi::CStr>,
+    {
+        let input_ptrs = Vec::with_capacity(input_names.len());
+        let output_ptrs = Vec::with_capacity(output_names.len());
+        let bound_inputs = (0..input_names.len()).map(|_| None).collect();
+        let bound_input_ptrs = vec![std::ptr::null(); input_names.len()];
+        PreparedRun {
+            session: self,
+            input_names,
+            output_names,
+            input_ptrs,
+            output_ptrs,
+            bound_inputs,
+            bound_input_ptrs,
+        }
+    }
+
+    /// Creates zero-filled `f32` output buffers sized for running the session with inputs of the given shapes, to pass
+    /// to e.g. [`Session::run_with_io_ref`](crate::Session::run_with_io_ref). `input_shapes` are in the order of the
+    /// model's inputs.
+    ///
+    /// ONNX Runtime doesn't expose shape inference, so each output dimension must either be fixed in the model or share
+    /// its symbolic name with an input dimension (i.e. both declared as `batch`), in which case it takes that input's
+    /// size. Outputs whose shapes depend on the values of the inputs return [`Error::UnresolvedOutputShape`]; these
+    /// need to be allocated by ONNX Runtime instead, i.e. with [`Session::run`](crate::Session::run).
+    ///
+    /// [`Error::UnresolvedOutputShape`]: crate::Error::UnresolvedOutputShape
+    pub fn prepare_outputs(&self, input_shapes: &[&[i64]]) -> crate::Result<Vec<RustOwnerValue<Vec<f32>>>, RunError> {
+        if input_shapes.len() != self.inputs.len() {
+            return Err(crate::Error::InputCountMismatch { names: self.inputs.len(), values: input_shapes.len() }.into());
+        }
+
+        let mut symbols = std::collections::HashMap::new();
+        for (i, (input, &shape)) in self.inputs.iter().zip(input_shapes).enumerate() {
+            let Some(dimensions) = input.input_type.tensor_dimensions() else {
+                return Err(crate::Error::InputNotTensor(input.name.clone()).into());
+            };
+            let fits = shape.len() == dimensions.len() && shape.iter().zip(dimensions).all(|(&actual, &expected)| expected < 0 || actual == expected);
+            if !fits {
+                return Err(crate::Error::InputShapeMismatch { name: input.name.clone(), expected: dimensions.clone(), actual: shape.to_vec() }.into());
+            }
+            for (symbol, &size) in self.input_symbolic_dimensions(i)?.into_iter().zip(shape) {
+                if let Some(symbol) = symbol {
+                    symbols.insert(symbol, size);
+                }
+            }
+        }
+
+        let mut outputs = Vec::with_capacity(self.outputs.len());
+        for (i, output) in self.outputs.iter().enumerate() {
+            let dimensions = match &output.output_type {
+                ValueType::Tensor { ty: TensorElementType::Float32, dimensions } => dimensions,
+                ValueType::Tensor { ty, .. } => {
+                    return Err(crate::Error::OutputTypeMismatch { name: output.name.clone(), expected: TensorElementType::Float32, got: *ty }.into());
+                }
+                _ => return Err(crate::Error::OutputNotTensor(output.name.clone()).into()),
+            };
+            let symbolic_dimensions = self.output_symbolic_dimensions(i)?;
+            let shape = dimensions
+                .iter()
+                .enumerate()
+                .map(|(d, &size)| match symbolic_dimensions.get(d) {
+                    _ if size >= 0 => Some(size),
+                    Some(Some(symbol)) => symbols.get(symbol).copied(),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| crate::Error::UnresolvedOutputShape { name: output.name.clone(), dimensions: dimensions.clone() })?;
+            outputs.push(RustOwnerValue::zeros(&shape)?);
+        }
+        Ok(outputs)
+    }
+
+    /// Returns an [`InputBuilder`] for the input named `name`, which checks the data & shape it is given against the
+    /// input's element type & dimensions in the model before creating the tensor.
+    ///
+    /// ```no_run
+    /// # use ort::Session;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let session = Session::builder()?.with_model_from_file("model.onnx")?;
+    /// let pixel_values = session
+    ///     .input_builder("pixel_values")?
+    ///     .data(vec![0.0f32; 3 * 224 * 224])
+    ///     .shape([1, 3, 224, 224])
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn input_builder(&self, name: &str) -> crate::Result<InputBuilder<'_>> {
+        let input = self.inputs.iter().find(|input| input.name == name).ok_or_else(|| crate::Error::UnknownInput(name.to_owned()))?;
+        match &input.input_type {
+            ValueType::Tensor { ty, dimensions } => Ok(InputBuilder {
+                name: &input.name,
+                element_type: *ty,
+                dimensions,
+                data: (),
+                shape: None,
+            }),
+            _ => Err(crate::Error::InputNotTensor(name.to_owned())),
+        }
+    }
+
+    /// Runs the session, reading `inputs` and writing into the pre-allocated `outputs` in place.
+    ///
+    /// ONNX Runtime supports concurrent calls to `Run` on the same session, and this method keeps no state in the
+    /// [`Session`](crate::Session) between calls (the pointer arrays passed to ONNX Runtime are built per call), so it
+    /// may be called from several threads at once, e.g. through an `Arc<Session>`, as long as each call has its own
+    /// outputs. The same holds for the other `run_with_*` methods.
     pub fn run_with_io_ref<I, O, SI, SO, CIn, COut, CNamesIn, CNamesOut>(&self,
                                                                          input_names: &Names<CNamesIn>,
                                                                          inputs: &[RustOwnerValue<CIn>],
@@ -491,27 +1151,34 @@ impl super::Session {
             SI: AsRef<std::ffi::CStr>,
             SO: AsRef<std::ffi::CStr>,
     {
+        self.run_with_io_ref_options(input_names, inputs, output_names, outputs, run_options.as_deref())
+    }
+
+    /// Like [`Session::run_with_io_ref`](crate::Session::run_with_io_ref), but with borrowed [`RunOptions`], so they
+    /// don't need to be put in an [`Arc`] when they're only used from the calling thread. An [`Arc`] is only needed
+    /// to terminate the run from another thread.
+    pub fn run_with_io_ref_options<I, O, SI, SO, CIn, COut, CNamesIn, CNamesOut>(&self,
+                                                                                 input_names: &Names<CNamesIn>,
+                                                                                 inputs: &[RustOwnerValue<CIn>],
+                                                                                 output_names: &Names<CNamesOut>,
+                                                                                 outputs: &mut [RustOwnerValue<COut>],
+                                                                                 run_options: Option<&RunOptions>) -> crate::Result<()>
+        where
+            CIn: std::ops::Deref<Target=[I]>,
+            COut: std::ops::DerefMut<Target=[O]>,
+            CNamesIn: std::ops::Deref<Target=[SI]>,
+            CNamesOut: std::ops::Deref<Target=[SO]>,
+            I: IntoTensorElementType + Debug + Clone + 'static,
+            O: IntoTensorElementType + Debug + Clone + 'static,
+            SI: AsRef<std::ffi::CStr>,
+            SO: AsRef<std::ffi::CStr>,
+    {
+        check_counts(input_names.len(), inputs.len(), output_names.len(), outputs.len())?;
+        self.check_input_types(input_names.names.iter().map(|name| name.as_ref()).zip(inputs.iter().map(|input| input.ptr())))?;
         // The C API expects pointers for the arrays (pointers to C-arrays)
         let input_ort_values: Vec<*const ort_sys::OrtValue> = inputs.iter().map(|a| a.ptr()).collect();
         let mut output_tensor_ptrs: Vec<*mut ort_sys::OrtValue> = outputs.iter_mut().map(|a| a.ptr_mut()).collect();
-        let run_options_ptr = if let Some(run_options) = &run_options {
-            run_options.run_options_ptr
-        } else {
-            std::ptr::null_mut()
-        };
-        ortsys![
-			unsafe Run(
-				self.inner.session_ptr,
-				run_options_ptr,
-				input_names.as_ptr(),
-				input_ort_values.as_ptr(),
-				input_ort_values.len() as _,
-				output_names.as_ptr(),
-				output_names.len() as _,
-				output_tensor_ptrs.as_mut_ptr()
-			) -> crate::Error::SessionRun
-		];
-        Ok(())
+        self.run_raw::<O>(&input_names.ptrs, &input_ort_values, &output_names.ptrs, &mut output_tensor_ptrs, run_options, |_| true)
     }
 
     pub fn run_with_values<I, O, SI, SO, CIn, COut, CNamesIn, CNamesOut>(&self,
@@ -530,8 +1197,426 @@ impl super::Session {
             SI: AsRef<std::ffi::CStr>,
             SO: AsRef<std::ffi::CStr>,
     {
-        // The C API expects pointers for the arrays (pointers to C-arrays)
-        let run_options_ptr = if let Some(run_options) = &run_options {
+        check_counts(input_names.len(), inputs.len(), output_names.len(), outputs.len())?;
+        self.check_input_types(input_names.names.iter().map(|name| name.as_ref()).zip(inputs.as_slice().iter().map(|input| input.ptr())))?;
+        self.run_raw::<O>(&input_names.ptrs, inputs.input_ptrs(), &output_names.ptrs, &mut outputs.ptrs, run_options.as_deref(), |_| true)
+    }
+
+    /// Like [`Session::run_with_values`](crate::Session::run_with_values), but returns each output paired with its name,
+    /// in the order of `output_names`, so callers don't need to track which position holds which output.
+    pub fn run_with_values_named<'o, I, O, SI, SO, CIn, COut, CNamesIn, CNamesOut>(&self,
+                                                                               input_names: &Names<CNamesIn>,
+                                                                               inputs: &Values<CIn>,
+                                                                               output_names: &'o Names<CNamesOut>,
+                                                                               outputs: &'o mut Values<COut>,
+                                                                               run_options: Option<Arc<RunOptions>>) -> crate::Result<Vec<(&'o str, &'o RustOwnerValue<COut>)>>
+        where
+            CIn: std::ops::Deref<Target=[I]>,
+            COut: std::ops::DerefMut<Target=[O]>,
+            CNamesIn: std::ops::Deref<Target=[SI]>,
+            CNamesOut: std::ops::Deref<Target=[SO]>,
+            I: IntoTensorElementType + Debug + Clone + 'static,
+            O: IntoTensorElementType + Debug + Clone + 'static,
+            SI: AsRef<std::ffi::CStr>,
+            SO: AsRef<std::ffi::CStr> + 'o,
+    {
+        self.run_with_values(input_names, inputs, output_names, outputs, run_options)?;
+        output_names
+            .names
+            .iter()
+            .zip(outputs.as_slice())
+            .map(|(name, output)| {
+                let name = name.as_ref().to_str().map_err(|e| crate::Error::FfiStringConversion(crate::ErrorInternal::Msg(e.to_string())))?;
+                Ok((name, output))
+            })
+            .collect()
+    }
+
+    /// Like [`Session::run_with_io_ref`](crate::Session::run_with_io_ref), but only some outputs need to be
+    /// pre-allocated.
+    ///
+    /// `Some` outputs are written into in place. For `None` outputs, ONNX Runtime allocates the value itself; these are
+    /// returned as `(output index, value)` pairs, in output order.
+    pub fn run_with_partial_outputs<I, O, SI, SO, CIn, COut, CNamesIn, CNamesOut>(&self,
+                                                                                  input_names: &Names<CNamesIn>,
+                                                                                  inputs: &[RustOwnerValue<CIn>],
+                                                                                  output_names: &Names<CNamesOut>,
+                                                                                  outputs: &mut [Option<RustOwnerValue<COut>>],
+                                                                                  run_options: Option<Arc<RunOptions>>) -> crate::Result<Vec<(usize, Value)>>
+        where
+            CIn: std::ops::Deref<Target=[I]>,
+            COut: std::ops::DerefMut<Target=[O]>,
+            CNamesIn: std::ops::Deref<Target=[SI]>,
+            CNamesOut: std::ops::Deref<Target=[SO]>,
+            I: IntoTensorElementType + Debug + Clone + 'static,
+            O: IntoTensorElementType + Debug + Clone + 'static,
+            SI: AsRef<std::ffi::CStr>,
+            SO: AsRef<std::ffi::CStr>,
+    {
+        check_counts(input_names.len(), inputs.len(), output_names.len(), outputs.len())?;
+        self.check_input_types(input_names.names.iter().map(|name| name.as_ref()).zip(inputs.iter().map(|input| input.ptr())))?;
+        let input_ort_values: Vec<*const ort_sys::OrtValue> = inputs.iter().map(|a| a.ptr()).collect();
+        // A null pointer tells ONNX Runtime to allocate the output itself.
+        let mut output_tensor_ptrs: Vec<*mut ort_sys::OrtValue> = outputs
+            .iter_mut()
+            .map(|a| a.as_mut().map_or(std::ptr::null_mut(), |a| a.ptr_mut()))
+            .collect();
+        self.run_raw::<O>(&input_names.ptrs, &input_ort_values, &output_names.ptrs, &mut output_tensor_ptrs, run_options.as_deref(), |i| outputs[i].is_some())?;
+        Ok(outputs
+            .iter()
+            .zip(output_tensor_ptrs)
+            .enumerate()
+            .filter(|(_, (provided, _))| provided.is_none())
+            .map(|(i, (_, ptr))| (i, unsafe { Value::from_raw(ptr, Arc::clone(&self.inner)) }))
+            .collect())
+    }
+
+    /// Like [`Session::run_with_partial_outputs`](crate::Session::run_with_partial_outputs), but pre-allocated outputs
+    /// are matched to the model's outputs by name rather than by position, so they stay correct if the order of the
+    /// outputs changes between versions of a model.
+    ///
+    /// Each `(name, buffer)` pair is written into in place. Every other output of the model is allocated by ONNX Runtime
+    /// and returned in the [`SessionOutputs`], in the model's output order. Returns
+    /// [`Error::UnknownOutput`](crate::Error::UnknownOutput) if the model has no output with one of the given names, and
+    /// [`Error::DuplicateOutput`](crate::Error::DuplicateOutput) if a name is given more than once.
+    pub fn run_with_named_outputs<I, O, SI, CIn, COut, CNamesIn>(&self,
+                                                                 input_names: &Names<CNamesIn>,
+                                                                 inputs: &[RustOwnerValue<CIn>],
+                                                                 outputs: &mut [(&str, &mut RustOwnerValue<COut>)],
+                                                                 run_options: Option<Arc<RunOptions>>) -> crate::Result<SessionOutputs<'_>>
+        where
+            CIn: std::ops::Deref<Target=[I]>,
+            COut: std::ops::DerefMut<Target=[O]>,
+            CNamesIn: std::ops::Deref<Target=[SI]>,
+            I: IntoTensorElementType + Debug + Clone + 'static,
+            O: IntoTensorElementType + Debug + Clone + 'static,
+            SI: AsRef<std::ffi::CStr>,
+    {
+        check_counts(input_names.len(), inputs.len(), 0, 0)?;
+        if let Some((name, _)) = outputs.iter().find(|(name, _)| !self.outputs.iter().any(|output| output.name == *name)) {
+            return Err(crate::Error::UnknownOutput(name.to_string()));
+        }
+        for (i, (name, _)) in outputs.iter().enumerate() {
+            if outputs[..i].iter().any(|(other, _)| other == name) {
+                return Err(crate::Error::DuplicateOutput(name.to_string()));
+            }
+        }
+        self.check_input_types(input_names.names.iter().map(|name| name.as_ref()).zip(inputs.iter().map(|input| input.ptr())))?;
+        let input_ort_values: Vec<*const ort_sys::OrtValue> = inputs.iter().map(|a| a.ptr()).collect();
+        let output_names: Vec<CString> = self.outputs.iter().map(|output| CString::new(output.name.as_str())).collect::<Result<_, _>>()?;
+        let output_name_ptrs: Vec<*const std::ffi::c_char> = output_names.iter().map(|name| name.as_ptr()).collect();
+        // A null pointer tells ONNX Runtime to allocate the output itself.
+        let mut output_tensor_ptrs: Vec<*mut ort_sys::OrtValue> = self
+            .outputs
+            .iter()
+            .map(|output| {
+                outputs
+                    .iter_mut()
+                    .find(|(name, _)| output.name == *name)
+                    .map_or(std::ptr::null_mut(), |(_, buffer)| buffer.ptr_mut())
+            })
+            .collect();
+        let provided = |name: &str| outputs.iter().any(|(provided, _)| *provided == name);
+        self.run_raw::<O>(&input_names.ptrs, &input_ort_values, &output_name_ptrs, &mut output_tensor_ptrs, run_options.as_deref(), |i| provided(&self.outputs[i].name))?;
+        let (allocated_names, allocated_values): (Vec<&str>, Vec<Value>) = self
+            .outputs
+            .iter()
+            .zip(output_tensor_ptrs)
+            .filter(|(output, _)| !provided(&output.name))
+            .map(|(output, ptr)| (output.name.as_str(), unsafe { Value::from_raw(ptr, Arc::clone(&self.inner)) }))
+            .unzip();
+        Ok(SessionOutputs::new(allocated_names.into_iter(), allocated_values))
+    }
+
+    /// Like [`Session::run_with_io_ref`](crate::Session::run_with_io_ref), but with inputs that are [`Value`]s, such as
+    /// the outputs of another session returned by [`Session::run_with_partial_outputs`](crate::Session::run_with_partial_outputs)
+    /// or [`Session::run`](crate::Session::run).
+    ///
+    /// This chains models in a pipeline: the values' `OrtValue`s are passed to ONNX Runtime as-is, so intermediate
+    /// tensors allocated on a device by the previous model are fed to the next one without a copy through host memory.
+    pub fn run_with_value_inputs<O, SI, SO, COut, CNamesIn, CNamesOut>(&self,
+                                                                       input_names: &Names<CNamesIn>,
+                                                                       inputs: &[&Value],
+                                                                       output_names: &Names<CNamesOut>,
+                                                                       outputs: &mut [RustOwnerValue<COut>],
+                                                                       run_options: Option<Arc<RunOptions>>) -> crate::Result<()>
+        where
+            COut: std::ops::DerefMut<Target=[O]>,
+            CNamesIn: std::ops::Deref<Target=[SI]>,
+            CNamesOut: std::ops::Deref<Target=[SO]>,
+            O: IntoTensorElementType + Debug + Clone + 'static,
+            SI: AsRef<std::ffi::CStr>,
+            SO: AsRef<std::ffi::CStr>,
+    {
+        check_counts(input_names.len(), inputs.len(), output_names.len(), outputs.len())?;
+        let input_ort_values: Vec<*const ort_sys::OrtValue> = inputs.iter().map(|a| a.ptr() as *const _).collect();
+        self.check_input_types(input_names.names.iter().map(|name| name.as_ref()).zip(input_ort_values.iter().copied()))?;
+        let mut output_tensor_ptrs: Vec<*mut ort_sys::OrtValue> = outputs.iter_mut().map(|a| a.ptr_mut()).collect();
+        self.run_raw::<O>(&input_names.ptrs, &input_ort_values, &output_names.ptrs, &mut output_tensor_ptrs, run_options.as_deref(), |_| true)
+    }
+
+    /// Like [`Session::run_with_io_ref`](crate::Session::run_with_io_ref), but inputs may be omitted by passing `None`,
+    /// e.g. to run a model without its optional `attention_mask`.
+    ///
+    /// ONNX Runtime rejects null input values, so omitted inputs are instead left out of the names passed to it. Only
+    /// inputs declared with an [`Optional`](ValueType::Optional) type, or initializers that aren't listed in
+    /// [`Session::inputs`](crate::Session::inputs), may be omitted; omitting any other input returns
+    /// [`Error::RequiredInputOmitted`](crate::Error::RequiredInputOmitted), and omitting a name that is neither an input
+    /// nor an [overridable initializer](crate::Session::overridable_initializer_names) returns
+    /// [`Error::UnknownInput`](crate::Error::UnknownInput), so a misspelled name isn't silently ignored.
+    pub fn run_with_optional_inputs<I, O, SI, SO, CIn, COut, CNamesIn, CNamesOut>(&self,
+                                                                                  input_names: &Names<CNamesIn>,
+                                                                                  inputs: &[Option<RustOwnerValue<CIn>>],
+                                                                                  output_names: &Names<CNamesOut>,
+                                                                                  outputs: &mut [RustOwnerValue<COut>],
+                                                                                  run_options: Option<Arc<RunOptions>>) -> crate::Result<()>
+        where
+            CIn: std::ops::Deref<Target=[I]>,
+            COut: std::ops::DerefMut<Target=[O]>,
+            CNamesIn: std::ops::Deref<Target=[SI]>,
+            CNamesOut: std::ops::Deref<Target=[SO]>,
+            I: IntoTensorElementType + Debug + Clone + 'static,
+            O: IntoTensorElementType + Debug + Clone + 'static,
+            SI: AsRef<std::ffi::CStr>,
+            SO: AsRef<std::ffi::CStr>,
+    {
+        check_counts(input_names.len(), inputs.len(), output_names.len(), outputs.len())?;
+        self.check_input_types(input_names.names.iter().zip(inputs).filter_map(|(name, input)| Some((name.as_ref(), input.as_ref()?.ptr()))))?;
+        let mut input_name_ptrs: Vec<*const std::ffi::c_char> = Vec::with_capacity(inputs.len());
+        let mut input_ort_values: Vec<*const ort_sys::OrtValue> = Vec::with_capacity(inputs.len());
+        for (name, input) in input_names.names.iter().zip(inputs) {
+            let name = name.as_ref();
+            match input {
+                Some(input) => {
+                    input_name_ptrs.push(name.as_ptr());
+                    input_ort_values.push(input.ptr());
+                }
+                None => {
+                    let name = name.to_string_lossy();
+                    match self.inputs.iter().find(|input| input.name == name) {
+                        Some(input) if !matches!(input.input_type, ValueType::Optional(_)) => {
+                            return Err(crate::Error::RequiredInputOmitted(name.into_owned()));
+                        }
+                        Some(_) => {}
+                        None => {
+                            if !self.overridable_initializer_names().iter().any(|initializer| *initializer == name) {
+                                return Err(crate::Error::UnknownInput(name.into_owned()));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let mut output_tensor_ptrs: Vec<*mut ort_sys::OrtValue> = outputs.iter_mut().map(|a| a.ptr_mut()).collect();
+        self.run_raw::<O>(&input_name_ptrs, &input_ort_values, &output_names.ptrs, &mut output_tensor_ptrs, run_options.as_deref(), |_| true)
+    }
+}
+
+/// Builds a [`RustOwnerValue`] for one of a session's inputs, validating it against the model's metadata. Created with
+/// [`Session::input_builder`](crate::Session::input_builder).
+#[derive(Debug)]
+pub struct InputBuilder<'s, Container = ()> {
+    name: &'s str,
+    element_type: TensorElementType,
+    dimensions: &'s [i64],
+    data: Container,
+    shape: Option<Vec<i64>>,
+}
+
+impl<'s, Container> InputBuilder<'s, Container> {
+    /// The element type the model expects for this input.
+    #[inline]
+    pub fn element_type(&self) -> TensorElementType {
+        self.element_type
+    }
+
+    /// The dimensions the model expects for this input; `-1` for a symbolic dimension.
+    #[inline]
+    pub fn dimensions(&self) -> &[i64] {
+        self.dimensions
+    }
+
+    /// Sets the data of the tensor.
+    pub fn data<C>(self, data: C) -> InputBuilder<'s, C> {
+        InputBuilder {
+            name: self.name,
+            element_type: self.element_type,
+            dimensions: self.dimensions,
+            data,
+            shape: self.shape,
+        }
+    }
+
+    /// Sets the shape of the tensor. May be omitted if none of the input's dimensions are symbolic.
+    pub fn shape(mut self, shape: impl AsRef<[i64]>) -> Self {
+        self.shape = Some(shape.as_ref().to_vec());
+        self
+    }
+}
+
+impl<'s, Container, T> InputBuilder<'s, Container>
+    where
+        Container: std::ops::Deref<Target=[T]>,
+        T: IntoTensorElementType + Debug + Clone + 'static,
+{
+    /// Creates the tensor, returning an error if its element type, rank, or any of its fixed dimensions don't match
+    /// the model's input.
+    pub fn build(self) -> crate::Result<RustOwnerValue<Container>, RunError> {
+        let element_type = T::into_tensor_element_type();
+        if element_type != self.element_type {
+            return Err(crate::Error::NonMatchingDataTypes { input: element_type, model: self.element_type }.into());
+        }
+        let shape = match self.shape {
+            Some(shape) => shape,
+            None if self.dimensions.iter().all(|&d| d >= 0) => self.dimensions.to_vec(),
+            None => return Err(crate::Error::MissingInputShape(self.name.to_owned()).into()),
+        };
+        let matches = shape.len() == self.dimensions.len() && shape.iter().zip(self.dimensions).all(|(&actual, &expected)| expected < 0 || actual == expected);
+        if !matches {
+            return Err(crate::Error::InputShapeMismatch {
+                name: self.name.to_owned(),
+                expected: self.dimensions.to_vec(),
+                actual: shape,
+            }
+            .into());
+        }
+        RustOwnerValue::new(&shape, self.data)
+    }
+}
+
+/// A run of a [`Session`](crate::Session) with fixed input & output names, created by
+/// [`Session::prepare_run`](crate::Session::prepare_run).
+///
+/// The names' C string pointers are computed once when the [`PreparedRun`] is created, and the buffers used to pass
+/// value pointers to ONNX Runtime are reused, so repeated calls to [`PreparedRun::run`] don't allocate, unlike
+/// [`Session::run_with_io_ref`](crate::Session::run_with_io_ref).
+///
+/// Inputs can also be kept in the [`PreparedRun`] itself with [`PreparedRun::rebind_input`] and run with
+/// [`PreparedRun::run_bound`], so that only the inputs that change between runs need to be recreated.
+pub struct PreparedRun<'s, CNamesIn, CNamesOut> {
+    session: &'s super::Session,
+    input_names: Names<CNamesIn>,
+    output_names: Names<CNamesOut>,
+    input_ptrs: Vec<*const ort_sys::OrtValue>,
+    output_ptrs: Vec<*mut ort_sys::OrtValue>,
+    /// Inputs bound with [`PreparedRun::rebind_input`], by index; each is a boxed [`RustOwnerValue`].
+    bound_inputs: Vec<Option<Box<dyn std::any::Any>>>,
+    bound_input_ptrs: Vec<*const ort_sys::OrtValue>,
+}
+
+impl<'s, SI, SO, CNamesIn, CNamesOut> PreparedRun<'s, CNamesIn, CNamesOut>
+    where
+        CNamesIn: std::ops::Deref<Target=[SI]>,
+        CNamesOut: std::ops::Deref<Target=[SO]>,
+        SI: AsRef<std::ffi::CStr>,
+        SO: AsRef<std::ffi::CStr>,
+{
+    #[inline]
+    pub fn input_names(&self) -> &Names<CNamesIn> {
+        &self.input_names
+    }
+
+    #[inline]
+    pub fn output_names(&self) -> &Names<CNamesOut> {
+        &self.output_names
+    }
+
+    /// Runs the session with the prepared names, writing into the pre-allocated `outputs`.
+    pub fn run<I, O, CIn, COut>(&mut self,
+                                inputs: &[RustOwnerValue<CIn>],
+                                outputs: &mut [RustOwnerValue<COut>],
+                                run_options: Option<Arc<RunOptions>>) -> crate::Result<()>
+        where
+            CIn: std::ops::Deref<Target=[I]>,
+            COut: std::ops::DerefMut<Target=[O]>,
+            I: IntoTensorElementType + Debug + Clone + 'static,
+            O: IntoTensorElementType + Debug + Clone + 'static,
+    {
+        check_counts(self.input_names.len(), inputs.len(), self.output_names.len(), outputs.len())?;
+        self.session.check_input_types(self.input_names.names.iter().map(|name| name.as_ref()).zip(inputs.iter().map(|input| input.ptr())))?;
+        self.input_ptrs.clear();
+        self.input_ptrs.extend(inputs.iter().map(|a| a.ptr()));
+        self.run_prepared(outputs, run_options)
+    }
+
+    /// Binds `value` as the input at `index`, dropping the value previously bound there, if any. The other bound inputs
+    /// are kept, so e.g. a server with variable batch sizes only needs to recreate the inputs whose shape changed.
+    ///
+    /// Returns [`Error::InputIndexOutOfRange`](crate::Error::InputIndexOutOfRange) if there is no input name at `index`.
+    pub fn rebind_input<I, CIn>(&mut self, index: usize, value: RustOwnerValue<CIn>) -> crate::Result<()>
+        where
+            CIn: std::ops::Deref<Target=[I]> + 'static,
+            I: IntoTensorElementType + Debug + Clone + 'static,
+    {
+        let Some(name) = self.input_names.names.get(index) else {
+            return Err(crate::Error::InputIndexOutOfRange { index, count: self.input_names.len() });
+        };
+        self.session.check_input_types(std::iter::once((name.as_ref(), value.ptr())))?;
+        self.bound_input_ptrs[index] = value.ptr();
+        self.bound_inputs[index] = Some(Box::new(value));
+        Ok(())
+    }
+
+    /// Runs the session with the inputs bound with [`PreparedRun::rebind_input`], writing into the pre-allocated
+    /// `outputs`. Every input must have been bound.
+    pub fn run_bound<O, COut>(&mut self,
+                              outputs: &mut [RustOwnerValue<COut>],
+                              run_options: Option<Arc<RunOptions>>) -> crate::Result<()>
+        where
+            COut: std::ops::DerefMut<Target=[O]>,
+            O: IntoTensorElementType + Debug + Clone + 'static,
+    {
+        let bound = self.bound_inputs.iter().filter(|input| input.is_some()).count();
+        check_counts(self.input_names.len(), bound, self.output_names.len(), outputs.len())?;
+        self.input_ptrs.clear();
+        self.input_ptrs.extend_from_slice(&self.bound_input_ptrs);
+        self.run_prepared(outputs, run_options)
+    }
+
+    /// Runs the session with the input pointers in `self.input_ptrs`.
+    fn run_prepared<O, COut>(&mut self,
+                             outputs: &mut [RustOwnerValue<COut>],
+                             run_options: Option<Arc<RunOptions>>) -> crate::Result<()>
+        where
+            COut: std::ops::DerefMut<Target=[O]>,
+            O: IntoTensorElementType + Debug + Clone + 'static,
+    {
+        self.output_ptrs.clear();
+        self.output_ptrs.extend(outputs.iter_mut().map(|a| a.ptr_mut()));
+        self.session.run_raw::<O>(&self.input_names.ptrs, &self.input_ptrs, &self.output_names.ptrs, &mut self.output_ptrs, run_options.as_deref(), |_| true)
+    }
+
+    /// Like [`PreparedRun::run`], but with inputs & outputs passed as [`Values`], whose pointer arrays are already
+    /// computed.
+    pub fn run_with_values<I, O, CIn, COut>(&self,
+                                            inputs: &Values<CIn>,
+                                            outputs: &mut Values<COut>,
+                                            run_options: Option<Arc<RunOptions>>) -> crate::Result<()>
+        where
+            CIn: std::ops::Deref<Target=[I]>,
+            COut: std::ops::DerefMut<Target=[O]>,
+            I: IntoTensorElementType + Debug + Clone + 'static,
+            O: IntoTensorElementType + Debug + Clone + 'static,
+    {
+        self.session.run_with_values(&self.input_names, inputs, &self.output_names, outputs, run_options)
+    }
+}
+
+impl super::Session {
+    /// Runs the session on raw name & value pointer arrays, then validates the pre-allocated outputs; all of the
+    /// `run_with_*` variants go through here, so run errors are labelled and outputs are checked in one place.
+    ///
+    /// The output at index `i` was pre-allocated by the caller if `preallocated(i)`; otherwise its pointer is null and
+    /// ONNX Runtime allocates it. The caller takes ownership of those on success; if validation fails, they are
+    /// released here.
+    fn run_raw<O: IntoTensorElementType>(&self,
+                                         input_names: &[*const std::ffi::c_char],
+                                         input_values: &[*const ort_sys::OrtValue],
+                                         output_names: &[*const std::ffi::c_char],
+                                         output_values: &mut [*mut ort_sys::OrtValue],
+                                         run_options: Option<&RunOptions>,
+                                         preallocated: impl Fn(usize) -> bool) -> crate::Result<()> {
+        let run_options_ptr = if let Some(run_options) = run_options {
             run_options.run_options_ptr
         } else {
             std::ptr::null_mut()
@@ -541,13 +1626,250 @@ impl super::Session {
 				self.inner.session_ptr,
 				run_options_ptr,
 				input_names.as_ptr(),
-				inputs.as_ptr(),
-				inputs.len() as _,
+				input_values.as_ptr(),
+				input_values.len() as _,
 				output_names.as_ptr(),
 				output_names.len() as _,
-				outputs.as_mut_ptr()
-			) -> crate::Error::SessionRun
+				output_values.as_mut_ptr()
+			) -> |e| crate::Error::SessionRun(self.inner.label_run_error(e))
 		];
+        let result = self.check_output_types::<O>(
+            output_names.iter()
+                .zip(output_values.iter())
+                .enumerate()
+                .filter(|(i, _)| preallocated(*i))
+                // SAFETY: the names are valid C strings, as ONNX Runtime has just read them
+                .map(|(_, (&name, &ptr))| (unsafe { std::ffi::CStr::from_ptr(name) }, ptr as *const _))
+        );
+        if result.is_err() {
+            for (i, &ptr) in output_values.iter().enumerate() {
+                if !preallocated(i) {
+                    ortsys![unsafe ReleaseValue(ptr)];
+                }
+            }
+        }
+        result
+    }
+
+    /// If enabled with [`SessionBuilder::with_input_type_validation`](crate::SessionBuilder::with_input_type_validation),
+    /// checks that each input's element type matches the one declared by the model.
+    fn check_input_types<'a>(&self, inputs: impl IntoIterator<Item=(&'a std::ffi::CStr, *const ort_sys::OrtValue)>) -> crate::Result<()> {
+        if !self.inner.validate_input_types {
+            return Ok(());
+        }
+        for (name, value_ptr) in inputs {
+            let name = name.to_string_lossy();
+            let expected = match self.inputs.iter().find(|input| input.name == name).map(|input| &input.input_type) {
+                Some(ValueType::Tensor { ty, .. }) => *ty,
+                // unknown inputs & non-tensor inputs are left for ONNX Runtime to reject
+                _ => continue,
+            };
+            let got = tensor_element_type(value_ptr)?;
+            if got != expected {
+                return Err(crate::Error::InputTypeMismatch { name: name.into_owned(), expected, got });
+            }
+        }
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// If enabled with [`SessionBuilder::with_output_type_validation`](crate::SessionBuilder::with_output_type_validation),
+    /// checks that each output's element type, both as reported by the value and as declared by the model, is `O`.
+    fn check_output_types<'a, O: IntoTensorElementType>(&self, outputs: impl IntoIterator<Item=(&'a std::ffi::CStr, *const ort_sys::OrtValue)>) -> crate::Result<()> {
+        if !self.inner.validate_output_types {
+            return Ok(());
+        }
+        let expected = O::into_tensor_element_type();
+        for (name, value_ptr) in outputs {
+            let name = name.to_string_lossy();
+            let declared = match self.outputs.iter().find(|output| output.name == name).map(|output| &output.output_type) {
+                Some(ValueType::Tensor { ty, .. }) => Some(*ty),
+                _ => None,
+            };
+            let got = match declared {
+                Some(declared) if declared != expected => declared,
+                _ => tensor_element_type(value_ptr)?,
+            };
+            if got != expected {
+                return Err(crate::Error::OutputTypeMismatch { name: name.into_owned(), expected, got });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `Run` reads `names.len()` entries from both the names and values arrays, so they must agree.
+fn check_counts(input_names: usize, inputs: usize, output_names: usize, outputs: usize) -> crate::Result<()> {
+    if input_names != inputs {
+        return Err(crate::Error::InputCountMismatch { names: input_names, values: inputs });
+    }
+    if output_names != outputs {
+        return Err(crate::Error::OutputCountMismatch { names: output_names, values: outputs });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MisimplementedU8(#[allow(dead_code)] u8);
+
+    impl IntoTensorElementType for MisimplementedU8 {
+        fn into_tensor_element_type() -> TensorElementType {
+            TensorElementType::Float32
+        }
+    }
+
+    #[test]
+    fn test_check_element_size() {
+        assert!(check_element_size::<f32>().is_ok());
+        assert!(check_element_size::<i64>().is_ok());
+        assert!(matches!(check_element_size::<MisimplementedU8>(), Err(RunError::Msg(_))));
+    }
+
+    #[test]
+    fn test_element_types() {
+        for (ty, size) in ELEMENT_TYPES {
+            assert_eq!(convert_to_onnx_el_type(ty as i32), Ok(ty));
+            assert_eq!(get_type_size(ty).ok(), size);
+        }
+        assert!(convert_to_onnx_el_type(-1).is_err());
+        // every element type that tensors can be created from must have a size
+        for ty in [
+            TensorElementType::Float32,
+            TensorElementType::Uint8,
+            TensorElementType::Int8,
+            TensorElementType::Uint16,
+            TensorElementType::Int16,
+            TensorElementType::Int32,
+            TensorElementType::Int64,
+            TensorElementType::Bool,
+            TensorElementType::Float64,
+            TensorElementType::Uint32,
+            TensorElementType::Uint64,
+        ] {
+            assert!(get_type_size(ty.into()).is_ok(), "{ty:?} has no size");
+        }
+        assert!(matches!(
+            get_type_size(ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING),
+            Err(crate::Error::UnsupportedElementType(TensorElementType::String))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "memmap2")]
+    fn test_from_mmap() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join(format!("ort-test-from-mmap-{}.bin", std::process::id()));
+        let data = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        std::fs::File::create(&path)?.write_all(&data.iter().flat_map(|x| x.to_ne_bytes()).collect::<Vec<_>>())?;
+        let mmap = unsafe { memmap2::Mmap::map(&std::fs::File::open(&path)?)? };
+        std::fs::remove_file(&path)?;
+
+        let value = RustOwnerValue::from_mmap(&[2, 3], mmap, TensorElementType::Float32)?;
+        assert_eq!(value.type_and_shape()?.dimensions, [2, 3]);
+        assert_eq!(value.reinterpret::<f32>()?, &data);
+        // the container is the mapping itself, so it stays mapped until the value is dropped
+        let mmap = value.into_container();
+        assert_eq!(mmap.len(), data.len() * std::mem::size_of::<f32>());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_element_type_helpers() {
+        assert_eq!(TensorElementType::Float32.size(), Some(4));
+        assert_eq!(TensorElementType::Bool.size(), Some(1));
+        assert_eq!(TensorElementType::String.size(), None);
+        assert!(TensorElementType::Float64.is_float() && !TensorElementType::Float64.is_integer());
+        assert!(TensorElementType::Uint8.is_integer() && !TensorElementType::Uint8.is_float());
+        assert!(!TensorElementType::Bool.is_integer() && !TensorElementType::Bool.is_float());
+        assert_eq!(TensorElementType::Float32.to_string(), "float");
+        assert_eq!(TensorElementType::Float64.to_string(), "double");
+    }
+
+    #[test]
+    fn test_any_type_unsized_element() {
+        let data = [0u8; 16];
+        assert!(matches!(
+            RustOwnerValue::with_any_type(&[2], &data[..], TensorElementType::String),
+            Err(RunError::OrtError(crate::Error::UnsupportedElementType(TensorElementType::String)))
+        ));
+        assert!(convert_to_onnx_el_type(1000).is_err());
+    }
+
+    #[test]
+    fn test_any_type_oversized_buffer() -> crate::Result<()> {
+        // 2 `bool`s followed by bytes that aren't part of the tensor, and wouldn't be valid `bool`s
+        let data = [1u8, 0, 2, 3];
+        let value = RustOwnerValue::with_any_type(&[2], &data[..], TensorElementType::Bool).unwrap();
+        assert_eq!(value.typed_bytes()?, [1, 0]);
+        assert_eq!(value.reinterpret::<bool>()?, [true, false]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_full_invalid_shape() {
+        // checked before anything is allocated
+        assert!(matches!(RustOwnerValue::full(&[2, -1], 0f32), Err(RunError::OrtError(crate::Error::InvalidShape(_)))));
+        assert!(matches!(
+            RustOwnerValue::<Vec<u8>>::zeros(&[i64::MAX, i64::MAX]),
+            Err(RunError::OrtError(crate::Error::InvalidShape(_)))
+        ));
+    }
+
+    #[test]
+    fn test_one_hot() {
+        assert!(matches!(RustOwnerValue::one_hot(4, 4), Err(RunError::Msg(_))));
+        assert!(matches!(RustOwnerValue::one_hot(4, -1), Err(RunError::Msg(_))));
+        assert!(matches!(RustOwnerValue::one_hot(0, 0), Err(RunError::Msg(_))));
+        let value = RustOwnerValue::one_hot(4, 2).unwrap();
+        assert_eq!(value.as_slice(), [0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(value.type_and_shape().unwrap().dimensions, [4]);
+    }
+
+    #[test]
+    fn test_check_densely_packed() {
+        assert!(check_densely_packed(&[1, 3, 224, 224], 3 * 224 * 224).is_ok());
+        assert!(check_densely_packed(&[], 1).is_ok());
+        assert!(check_densely_packed(&[2, 0], 0).is_ok());
+        // e.g. rows padded to a pitch of 256 elements
+        assert!(matches!(
+            check_densely_packed(&[224, 224], 224 * 256),
+            Err(crate::Error::NotDenselyPacked { expected: 50176, actual: 57344, .. })
+        ));
+        assert!(matches!(check_densely_packed(&[-1, 3], 3), Err(crate::Error::InvalidShape(_))));
+        assert!(matches!(check_densely_packed(&[i64::MAX, i64::MAX], 0), Err(crate::Error::InvalidShape(_))));
+    }
+
+    #[test]
+    fn test_data_length_mismatch() {
+        // the length is checked before ONNX Runtime is involved
+        let too_short = RustOwnerValue::new(&[2, 3], vec![0f32; 5]);
+        assert!(matches!(
+            too_short,
+            Err(RunError::OrtError(crate::Error::NotDenselyPacked { expected: 6, actual: 5, .. }))
+        ));
+        let too_long = RustOwnerValue::new_exact(&[2, 3], vec![0f32; 7]);
+        assert!(matches!(
+            too_long,
+            Err(RunError::OrtError(crate::Error::NotDenselyPacked { expected: 6, actual: 7, .. }))
+        ));
+        let mut data = [0i64; 5];
+        let too_short = RustOwnerValue::new_mut(&[2, 3], &mut data[..]);
+        assert!(matches!(
+            too_short,
+            Err(RunError::OrtError(crate::Error::NotDenselyPacked { expected: 6, actual: 5, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_single_name() {
+        for names in [Names::single("input"), Names::from("input"), Names::from(String::from("input"))] {
+            assert_eq!(names.len(), 1);
+            assert_eq!(names.names[0].to_str(), Ok("input"));
+            assert_eq!(names.ptrs[0], names.names[0].as_ptr());
+        }
+    }
+}
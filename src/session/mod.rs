@@ -5,6 +5,7 @@ use std::os::unix::ffi::OsStrExt;
 #[cfg(target_family = "windows")]
 use std::os::windows::ffi::OsStrExt;
 use std::{
+	any::Any,
 	ffi::CString,
 	fmt,
 	marker::PhantomData,
@@ -12,7 +13,10 @@ use std::{
 	os::raw::c_char,
 	path::Path,
 	ptr,
-	sync::{atomic::Ordering, Arc}
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc
+	}
 };
 #[cfg(feature = "fetch-models")]
 use std::{path::PathBuf, time::Duration};
@@ -23,14 +27,14 @@ use super::{
 	api, char_p_to_string,
 	environment::get_environment,
 	error::{assert_non_null_pointer, assert_null_pointer, status_to_result, Error, ErrorInternal, Result},
-	execution_providers::{apply_execution_providers, ExecutionProviderDispatch},
+	execution_providers::{apply_execution_providers, CPUExecutionProvider, CUDAExecutionProvider, DevicePolicy, ExecutionProvider, ExecutionProviderDispatch},
 	extern_system_fn,
 	io_binding::IoBinding,
-	memory::Allocator,
+	memory::{Allocator, ArenaCfg, MemoryInfo},
 	metadata::ModelMetadata,
 	ortsys,
 	value::{Value, ValueType},
-	AllocatorType, GraphOptimizationLevel, MemType
+	AllocatorType, GraphOptimizationLevel, IntoTensorElementType, MemType, RustOwnerValue, WarningCapture
 };
 use crate::environment::Environment;
 
@@ -64,8 +68,13 @@ pub struct SessionBuilder {
 	allocator: AllocatorType,
 	memory_type: MemType,
 	#[cfg(feature = "custom-ops")]
-	custom_runtime_handles: Vec<*mut std::os::raw::c_void>,
-	execution_providers: Vec<ExecutionProviderDispatch>
+	custom_runtime_handles: Vec<Arc<LibHandle>>,
+	execution_providers: Vec<ExecutionProviderDispatch>,
+	log_id: Option<String>,
+	validate_input_types: bool,
+	validate_output_types: bool,
+	strict_model_validation: bool,
+	initializers: Vec<Arc<dyn Any>>
 }
 
 impl fmt::Debug for SessionBuilder {
@@ -73,6 +82,11 @@ impl fmt::Debug for SessionBuilder {
 		f.debug_struct("SessionBuilder")
 			.field("allocator", &self.allocator)
 			.field("memory_type", &self.memory_type)
+			.field("log_id", &self.log_id)
+			.field("validate_input_types", &self.validate_input_types)
+			.field("validate_output_types", &self.validate_output_types)
+			.field("strict_model_validation", &self.strict_model_validation)
+			.field("initializers", &self.initializers.len())
 			.finish()
 	}
 }
@@ -88,7 +102,13 @@ impl Clone for SessionBuilder {
 			memory_type: self.memory_type,
 			#[cfg(feature = "custom-ops")]
 			custom_runtime_handles: self.custom_runtime_handles.clone(),
-			execution_providers: self.execution_providers.clone()
+			execution_providers: self.execution_providers.clone(),
+			log_id: self.log_id.clone(),
+			validate_input_types: self.validate_input_types,
+			validate_output_types: self.validate_output_types,
+			strict_model_validation: self.strict_model_validation,
+			// the cloned options refer to the same initializer values
+			initializers: self.initializers.clone()
 		}
 	}
 }
@@ -96,11 +116,6 @@ impl Clone for SessionBuilder {
 impl Drop for SessionBuilder {
 	#[tracing::instrument]
 	fn drop(&mut self) {
-		#[cfg(feature = "custom-ops")]
-		for &handle in self.custom_runtime_handles.iter() {
-			close_lib_handle(handle);
-		}
-
 		if !self.session_options_ptr.is_null() {
 			ortsys![unsafe ReleaseSessionOptions(self.session_options_ptr)];
 		}
@@ -119,7 +134,12 @@ impl SessionBuilder {
 			memory_type: MemType::Default,
 			#[cfg(feature = "custom-ops")]
 			custom_runtime_handles: Vec::new(),
-			execution_providers: Vec::new()
+			execution_providers: Vec::new(),
+			log_id: None,
+			validate_input_types: false,
+			validate_output_types: false,
+			strict_model_validation: false,
+			initializers: Vec::new()
 		})
 	}
 
@@ -166,6 +186,111 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Adds the CUDA execution provider, with default options, to run on the user-managed CUDA stream `stream`. Use
+	/// [`CUDAExecutionProvider::with_compute_stream`] to configure other options as well; see there for the caller's
+	/// synchronization responsibilities.
+	///
+	/// # Safety
+	///
+	/// `stream` must be a valid CUDA stream, and must outlive all sessions created with this builder.
+	pub unsafe fn with_cuda_compute_stream(mut self, stream: *mut std::ffi::c_void) -> Result<Self> {
+		self.execution_providers.push(CUDAExecutionProvider::default().with_compute_stream(stream).build());
+		Ok(self)
+	}
+
+	/// Adds the DirectML execution provider for the GPU with the given device ID (the adapter index as enumerated by
+	/// DXGI, `0` being the default adapter), for GPU inference on Windows without CUDA.
+	///
+	/// DirectML doesn't support ONNX Runtime's memory pattern optimization or parallel execution, and fails at session
+	/// creation or run time if either is enabled, so this also disables both.
+	#[cfg(windows)]
+	#[cfg_attr(docsrs, doc(cfg(windows)))]
+	pub fn with_directml(self, device_id: i32) -> Result<Self> {
+		let mut builder = self.with_memory_pattern(false)?.with_parallel_execution(false)?;
+		builder
+			.execution_providers
+			.push(crate::DirectMLExecutionProvider::default().with_device_id(device_id).build());
+		Ok(builder)
+	}
+
+	/// Adds the NNAPI execution provider with the given options, for on-device inference on Android. See
+	/// [`NNAPIExecutionProvider`](crate::NNAPIExecutionProvider) for which options make nodes fall back to the CPU.
+	///
+	/// ```ignore
+	/// let session = Session::builder()?
+	/// 	.with_nnapi(NNAPIExecutionProvider::default().with_fp16())?
+	/// 	.with_model_from_file("model.onnx")?;
+	/// ```
+	#[cfg(target_os = "android")]
+	#[cfg_attr(docsrs, doc(cfg(target_os = "android")))]
+	pub fn with_nnapi(mut self, nnapi: crate::NNAPIExecutionProvider) -> Result<Self> {
+		self.execution_providers.push(nnapi.build());
+		Ok(self)
+	}
+
+	/// Sets an identifier for the session, used to tell apart the logs & errors of multiple sessions. It is passed to
+	/// ONNX Runtime's own logs, and errors from running the session are prefixed with it.
+	pub fn with_log_id(mut self, log_id: impl Into<String>) -> Result<Self> {
+		let log_id = log_id.into();
+		let log_id_c = CString::new(log_id.as_str())?;
+		ortsys![unsafe SetSessionLogId(self.session_options_ptr, log_id_c.as_ptr()) -> Error::CreateSessionOptions];
+		self.log_id = Some(log_id);
+		Ok(self)
+	}
+
+	/// Checks the element type of each input passed to [`Session::run_with_io_ref`] & co. against the type the model
+	/// declares for it before running, returning [`Error::InputTypeMismatch`] instead of ONNX Runtime's less helpful
+	/// error. This costs an extra FFI call per input per run, so it is disabled by default.
+	pub fn with_input_type_validation(mut self, enable: bool) -> Result<Self> {
+		self.validate_input_types = enable;
+		Ok(self)
+	}
+
+	/// After [`Session::run_with_io_ref`] & co. return, checks that each pre-allocated output's element type matches
+	/// both the element type `O` of its container and the type the model declares for it, returning
+	/// [`Error::OutputTypeMismatch`] otherwise. This catches output buffers that weren't updated along with the model.
+	/// Like [`SessionBuilder::with_input_type_validation`], it is disabled by default.
+	///
+	/// Outputs created from raw bytes with [`RustOwnerValue::with_any_type_mut`](crate::RustOwnerValue::with_any_type_mut)
+	/// have `O = u8`, so they are reported as mismatches unless the output really is `Uint8`.
+	pub fn with_output_type_validation(mut self, enable: bool) -> Result<Self> {
+		self.validate_output_types = enable;
+		Ok(self)
+	}
+
+	/// Fails session creation with [`Error::ModelValidation`] if ONNX Runtime logs any warnings while loading the model,
+	/// such as for duplicate initializer names or unused initializers, to catch malformed models before they produce
+	/// subtly wrong results. Disabled by default.
+	///
+	/// Enabling this sets the session's log severity level to warning, so that warnings are logged even if the
+	/// environment would otherwise filter them out. Warnings are captured through the session's log ID; if one was set
+	/// with [`SessionBuilder::with_log_id`] it should be unique among sessions being created at the same time,
+	/// otherwise a unique one is generated.
+	pub fn strict_model_validation(mut self, enable: bool) -> Result<Self> {
+		if enable {
+			ortsys![unsafe SetSessionLogSeverityLevel(self.session_options_ptr, ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_WARNING as _) -> Error::CreateSessionOptions];
+		}
+		self.strict_model_validation = enable;
+		Ok(self)
+	}
+
+	/// Overrides the initializer (i.e. weight) `name` of the model with `data`, e.g. to swap in fine-tuned weights without
+	/// re-exporting the model. The tensor is created like [`RustOwnerValue::new`] and kept alive by the builder and
+	/// every session created from it, since ONNX Runtime reads the data in place.
+	pub fn override_initializer<T>(mut self, name: &str, shape: &[i64], data: Vec<T>) -> Result<Self>
+	where
+		T: IntoTensorElementType + fmt::Debug + Clone + 'static
+	{
+		let value = RustOwnerValue::new(shape, data).map_err(|source| Error::CreateInitializer {
+			name: name.to_owned(),
+			source: Box::new(source)
+		})?;
+		let name_c = CString::new(name)?;
+		ortsys![unsafe AddInitializer(self.session_options_ptr, name_c.as_ptr(), value.ptr()) -> Error::CreateSessionOptions];
+		self.initializers.push(Arc::new(value));
+		Ok(self)
+	}
+
 	/// Configure the session to use a number of threads to parallelize the execution within nodes. If ONNX Runtime was
 	/// built with OpenMP (as is the case with Microsoft's prebuilt binaries), this will have no effect on the number of
 	/// threads used. Instead, you can configure the number of threads OpenMP uses via the `OMP_NUM_THREADS` environment
@@ -203,6 +328,24 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Enable/disable spinning of the intra-op thread pool's threads while they wait for work. Spinning is enabled by
+	/// default, which lowers latency but keeps the threads busy between runs; disable it for background or batch
+	/// workloads that should yield the CPU to other work.
+	///
+	/// ONNX Runtime only reads this when the session's thread pools are created, so it can't be changed per run. To
+	/// serve both latency-critical and throughput-oriented traffic, create a session for each.
+	pub fn with_intra_op_spinning(self, allow_spinning: bool) -> Result<Self> {
+		self.add_config_entry("session.intra_op.allow_spinning", if allow_spinning { "1" } else { "0" })?;
+		Ok(self)
+	}
+
+	/// Enable/disable spinning of the inter-op thread pool's threads, which are only used when parallel execution is
+	/// enabled; see [`SessionBuilder::with_intra_op_spinning`].
+	pub fn with_inter_op_spinning(self, allow_spinning: bool) -> Result<Self> {
+		self.add_config_entry("session.inter_op.allow_spinning", if allow_spinning { "1" } else { "0" })?;
+		Ok(self)
+	}
+
 	/// Enable/disable the parallel execution mode for this session. By default, this is disabled.
 	///
 	/// Parallel execution can improve performance for models with many branches, at the cost of higher memory usage.
@@ -239,6 +382,10 @@ impl SessionBuilder {
 	}
 
 	/// Enables/disables memory pattern optimization. Disable it if the input size varies, i.e., dynamic batch
+	///
+	/// Memory pattern optimization records the allocations made during a run and pre-allocates a single block for
+	/// subsequent runs with the same input shapes. With dynamic shapes the recorded pattern is frequently invalidated,
+	/// which can cause inconsistent run times. Enabled by default.
 	pub fn with_memory_pattern(self, enable: bool) -> Result<Self> {
 		if enable {
 			ortsys![unsafe EnableMemPattern(self.session_options_ptr) -> Error::CreateSessionOptions];
@@ -248,6 +395,80 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Disables the CPU memory arena, so that CPU memory is allocated & freed directly by the device allocator rather
+	/// than retained in a pool between runs.
+	pub fn with_arena_disabled(self) -> Result<Self> {
+		self.with_cpu_mem_arena(false)
+	}
+
+	/// Enables/disables the CPU memory arena. Enabled by default. See [`SessionBuilder::with_arena_disabled`].
+	pub fn with_cpu_mem_arena(self, enable: bool) -> Result<Self> {
+		if enable {
+			ortsys![unsafe EnableCpuMemArena(self.session_options_ptr) -> Error::CreateSessionOptions];
+		} else {
+			ortsys![unsafe DisableCpuMemArena(self.session_options_ptr) -> Error::CreateSessionOptions];
+		}
+		Ok(self)
+	}
+
+	/// Configures the CPU memory arena with the given sizes (in bytes):
+	/// - `initial_chunk_bytes`: the size of the first allocation made by the arena.
+	/// - `max_dead_bytes_per_chunk`: the threshold of unused space in a chunk above which the chunk is split.
+	/// - `initial_growth_chunk_bytes`: the size of the first allocation made after the initial chunk is exhausted, when
+	///   using the next-power-of-two extend strategy.
+	///
+	/// ONNX Runtime only allows custom arena configurations for allocators shared through the environment, so this
+	/// registers an arena allocator with the global environment and configures the session to use it. Because only one
+	/// CPU allocator can be registered with an environment, subsequent calls will return an error; all sessions
+	/// created with this option share the arena.
+	pub fn with_arena_config(self, initial_chunk_bytes: usize, max_dead_bytes_per_chunk: usize, initial_growth_chunk_bytes: usize) -> Result<Self> {
+		let env = get_environment()?;
+		let memory_info = MemoryInfo::new_cpu(AllocatorType::Arena, MemType::Default)?;
+		let arena_cfg = ArenaCfg::new(initial_chunk_bytes, max_dead_bytes_per_chunk, initial_growth_chunk_bytes)?;
+		env.create_and_register_allocator(&memory_info, Some(&arena_cfg))?;
+		self.use_env_allocators()
+	}
+
+	/// Makes the session use the allocators registered with the environment via
+	/// [`Environment::create_and_register_allocator`] instead of creating its own, so that multiple sessions can share
+	/// one arena.
+	///
+	/// [`Environment::create_and_register_allocator`]: crate::Environment::create_and_register_allocator
+	pub fn use_env_allocators(self) -> Result<Self> {
+		self.add_config_entry("session.use_env_allocators", "1")?;
+		Ok(self)
+	}
+
+	/// Sets an arbitrary session configuration entry, for options that don't have a dedicated method on the builder.
+	/// See [`onnxruntime_session_options_config_keys.h`](https://github.com/microsoft/onnxruntime/blob/v1.16.3/include/onnxruntime/core/session/onnxruntime_session_options_config_keys.h)
+	/// for the keys ONNX Runtime supports.
+	///
+	/// ```no_run
+	/// # use ort::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.config_entry("session.intra_op.allow_spinning", "0")?
+	/// 	.with_model_from_file("model.onnx")?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	///
+	/// Committing the builder consumes it, so the options can't be changed once the session is created:
+	///
+	/// ```compile_fail
+	/// # use ort::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let builder = Session::builder()?;
+	/// let session = builder.with_model_from_file("model.onnx")?;
+	/// let builder = builder.config_entry("session.intra_op.allow_spinning", "0")?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn config_entry(self, key: impl AsRef<str>, value: impl AsRef<str>) -> Result<Self> {
+		self.add_config_entry(key.as_ref(), value.as_ref())?;
+		Ok(self)
+	}
+
 	/// Set the session's allocator. Defaults to [`AllocatorType::Device`].
 	pub fn with_allocator(mut self, allocator: AllocatorType) -> Result<Self> {
 		self.allocator = allocator;
@@ -263,8 +484,20 @@ impl SessionBuilder {
 	/// Registers a custom operator library with the given library path in the session.
 	#[cfg(feature = "custom-ops")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "custom-ops")))]
-	pub fn with_custom_ops_lib(mut self, lib_path: impl AsRef<str>) -> Result<Self> {
-		let path_cstr = CString::new(lib_path.as_ref())?;
+	pub fn with_custom_ops_lib(self, lib_path: impl AsRef<str>) -> Result<Self> {
+		self.with_custom_ops_library(lib_path.as_ref())
+	}
+
+	/// Registers a compiled custom operator library (`.so`/`.dylib`/`.dll`) with the session.
+	///
+	/// The library is kept loaded for as long as any [`Session`] created from this builder is alive.
+	#[cfg(feature = "custom-ops")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "custom-ops")))]
+	pub fn with_custom_ops_library(mut self, lib_path: impl AsRef<Path>) -> Result<Self> {
+		#[cfg(not(target_family = "windows"))]
+		let path_cstr = CString::new(lib_path.as_ref().as_os_str().as_bytes())?;
+		#[cfg(target_family = "windows")]
+		let path_cstr = CString::new(lib_path.as_ref().to_string_lossy().as_bytes())?;
 
 		let mut handle: *mut ::std::os::raw::c_void = std::ptr::null_mut();
 
@@ -281,7 +514,7 @@ impl SessionBuilder {
 			return Err(e);
 		}
 
-		self.custom_runtime_handles.push(handle);
+		self.custom_runtime_handles.push(Arc::new(LibHandle(handle)));
 
 		Ok(self)
 	}
@@ -383,6 +616,13 @@ impl SessionBuilder {
 		}
 	}
 
+	fn add_config_entry(&self, key: &str, value: &str) -> Result<()> {
+		let key = CString::new(key)?;
+		let value = CString::new(value)?;
+		ortsys![unsafe AddSessionConfigEntry(self.session_options_ptr, key.as_ptr(), value.as_ptr()) -> Error::CreateSessionOptions];
+		Ok(())
+	}
+
 	// TODO: Add all functions changing the options.
 	//       See all OrtApi methods taking a `options: *mut OrtSessionOptions`.
 
@@ -418,8 +658,10 @@ impl SessionBuilder {
 
 		let env_ptr = env.env_ptr.load(Ordering::Relaxed);
 
+		let warning_capture = self.start_warning_capture()?;
 		let mut session_ptr: *mut ort_sys::OrtSession = std::ptr::null_mut();
-		ortsys![unsafe CreateSession(env_ptr, model_path.as_ptr(), self.session_options_ptr, &mut session_ptr) -> Error::CreateSession; nonNull(session_ptr)];
+		ortsys![unsafe CreateSession(env_ptr, model_path.as_ptr(), self.session_options_ptr, &mut session_ptr) -> Error::create_session; nonNull(session_ptr)];
+		finish_warning_capture(warning_capture, session_ptr)?;
 
 		let allocator = Allocator::default();
 
@@ -432,18 +674,94 @@ impl SessionBuilder {
 		let outputs = (0..num_output_nodes)
 			.map(|i| dangerous::extract_output(session_ptr, allocator.ptr, i))
 			.collect::<Result<Vec<Output>>>()?;
+		let overridable_initializers = (0..dangerous::extract_overridable_initializers_count(session_ptr)?)
+			.map(|i| dangerous::extract_overridable_initializer_name(session_ptr, allocator.ptr, i as _))
+			.collect::<Result<Vec<String>>>()?;
 
 		Ok(Session {
 			inner: Arc::new(SharedSessionInner {
 				session_ptr,
 				allocator,
-				_environment: Arc::clone(env)
+				#[cfg(feature = "custom-ops")]
+				_custom_runtime_handles: self.custom_runtime_handles.clone(),
+				_environment: Arc::clone(env),
+				log_id: self.log_id.clone(),
+				validate_input_types: self.validate_input_types,
+				validate_output_types: self.validate_output_types,
+				_initializers: self.initializers.clone()
 			}),
 			inputs,
-			outputs
+			outputs,
+			overridable_initializers
 		})
 	}
 
+	/// Loads an ONNX model from a file and builds the session, trying each of the builder's
+	/// [execution providers](SessionBuilder::with_execution_providers) in order until one succeeds.
+	///
+	/// Unlike [`SessionBuilder::with_model_from_file`], which registers every execution provider at once and silently
+	/// falls back to CPU if none can be registered, a session is created with only one execution provider at a time;
+	/// if either registering the provider or creating the session fails, the error is logged and the next provider is
+	/// tried. If no execution providers were configured, only the CPU execution provider is attempted.
+	///
+	/// Returns the session along with the execution provider it was created with, or the last error encountered if no
+	/// execution provider succeeded.
+	pub fn with_model_from_file_with_fallback<P>(self, model_filepath: P) -> Result<(Session, ExecutionProviderDispatch)>
+	where
+		P: AsRef<Path>
+	{
+		let model_filepath = model_filepath.as_ref();
+		let execution_providers = if self.execution_providers.is_empty() {
+			vec![CPUExecutionProvider::default().build()]
+		} else {
+			self.execution_providers.clone()
+		};
+
+		let mut last_error = None;
+		for ep in execution_providers {
+			let mut builder = self.clone();
+			builder.execution_providers = Vec::new();
+			let session = ep.register(&builder).and_then(|_| builder.with_model_from_file(model_filepath));
+			match session {
+				Ok(session) => {
+					tracing::info!("Created session with `{}`", ep.as_str());
+					return Ok((session, ep));
+				}
+				Err(e) => {
+					tracing::warn!("Failed to create session with `{}`: {e}", ep.as_str());
+					last_error = Some(e);
+				}
+			}
+		}
+		Err(last_error.expect("at least one execution provider is always attempted"))
+	}
+
+	/// Loads an ONNX model from a file and builds the session, trying the execution providers of `policy` in order, as
+	/// with [`SessionBuilder::with_model_from_file_with_fallback`]. The policy replaces any execution providers
+	/// configured on the builder; an empty policy only attempts the CPU execution provider.
+	///
+	/// Returns the session along with the execution provider (and so the device) it was created with.
+	pub fn with_model_from_file_with_policy<P>(mut self, policy: &DevicePolicy, model_filepath: P) -> Result<(Session, ExecutionProviderDispatch)>
+	where
+		P: AsRef<Path>
+	{
+		self.execution_providers = policy.execution_providers().to_vec();
+		self.with_model_from_file_with_fallback(model_filepath)
+	}
+
+	/// Loads a pre-optimized model in the [ORT format](https://onnxruntime.ai/docs/performance/model-optimizations/ort-format-models.html)
+	/// from a file and builds the session.
+	///
+	/// [`SessionBuilder::with_model_from_file`] infers the format from the `.ort` extension; this method forces the ORT
+	/// format regardless of the file's extension.
+	pub fn with_model_from_ort_format<P>(self, model_filepath: P) -> Result<Session>
+	where
+		P: AsRef<Path>
+	{
+		self.add_config_entry("session.load_model_format", "ORT")?;
+		self.with_model_from_file(model_filepath)
+	}
+
 	/// Load an ONNX graph from memory and commit the session
 	/// For `.ort` models, we enable `session.use_ort_model_bytes_directly`.
 	/// For more information, check [Load ORT format model from an in-memory byte array](https://onnxruntime.ai/docs/performance/model-optimizations/ort-format-models.html#load-ort-format-model-from-an-in-memory-byte-array).
@@ -467,6 +785,25 @@ impl SessionBuilder {
 		Ok(InMemorySession { session, phantom: PhantomData })
 	}
 
+	/// Starts capturing the warnings logged while creating the session if strict model validation is enabled, giving
+	/// the session a unique log ID to capture them by if it doesn't have one.
+	fn start_warning_capture(&self) -> Result<Option<WarningCapture>> {
+		if !self.strict_model_validation {
+			return Ok(None);
+		}
+		let log_id = match &self.log_id {
+			Some(log_id) => log_id.clone(),
+			None => {
+				static NEXT_LOG_ID: AtomicUsize = AtomicUsize::new(0);
+				let log_id = format!("ort-strict-{}", NEXT_LOG_ID.fetch_add(1, Ordering::Relaxed));
+				let log_id_c = CString::new(log_id.as_str())?;
+				ortsys![unsafe SetSessionLogId(self.session_options_ptr, log_id_c.as_ptr()) -> Error::CreateSessionOptions];
+				log_id
+			}
+		};
+		Ok(Some(WarningCapture::start(log_id)))
+	}
+
 	/// Load an ONNX graph from memory and commit the session.
 	pub fn with_model_from_memory(self, model_bytes: &[u8]) -> Result<Session> {
 		let mut session_ptr: *mut ort_sys::OrtSession = std::ptr::null_mut();
@@ -478,10 +815,12 @@ impl SessionBuilder {
 
 		let model_data = model_bytes.as_ptr() as *const std::ffi::c_void;
 		let model_data_length = model_bytes.len();
+		let warning_capture = self.start_warning_capture()?;
 		ortsys![
-			unsafe CreateSessionFromArray(env_ptr, model_data, model_data_length as _, self.session_options_ptr, &mut session_ptr) -> Error::CreateSession;
+			unsafe CreateSessionFromArray(env_ptr, model_data, model_data_length as _, self.session_options_ptr, &mut session_ptr) -> Error::create_session;
 			nonNull(session_ptr)
 		];
+		finish_warning_capture(warning_capture, session_ptr)?;
 
 		let allocator = Allocator::default();
 
@@ -494,27 +833,71 @@ impl SessionBuilder {
 		let outputs = (0..num_output_nodes)
 			.map(|i| dangerous::extract_output(session_ptr, allocator.ptr, i))
 			.collect::<Result<Vec<Output>>>()?;
+		let overridable_initializers = (0..dangerous::extract_overridable_initializers_count(session_ptr)?)
+			.map(|i| dangerous::extract_overridable_initializer_name(session_ptr, allocator.ptr, i as _))
+			.collect::<Result<Vec<String>>>()?;
 
 		let session = Session {
 			inner: Arc::new(SharedSessionInner {
 				session_ptr,
 				allocator,
-				_environment: Arc::clone(env)
+				#[cfg(feature = "custom-ops")]
+				_custom_runtime_handles: self.custom_runtime_handles.clone(),
+				_environment: Arc::clone(env),
+				log_id: self.log_id.clone(),
+				validate_input_types: self.validate_input_types,
+				validate_output_types: self.validate_output_types,
+				_initializers: self.initializers.clone()
 			}),
 			inputs,
-			outputs
+			outputs,
+			overridable_initializers
 		};
 		Ok(session)
 	}
 }
 
+/// Releases the newly created session and returns [`Error::ModelValidation`] if any warnings were captured while
+/// creating it.
+fn finish_warning_capture(warning_capture: Option<WarningCapture>, session_ptr: *mut ort_sys::OrtSession) -> Result<()> {
+	let warnings = warning_capture.map(WarningCapture::finish).unwrap_or_default();
+	if warnings.is_empty() {
+		return Ok(());
+	}
+	ortsys![unsafe ReleaseSession(session_ptr)];
+	Err(Error::ModelValidation(warnings))
+}
+
 /// Holds onto a C session and its allocator. This is wrapped in an [`Arc`] to ensure that [`Value`]s returned by the
 /// session keep their memory alive until all references to the session are dropped.
 #[derive(Debug)]
 pub struct SharedSessionInner {
 	pub(crate) session_ptr: *mut ort_sys::OrtSession,
 	allocator: Allocator,
-	_environment: Arc<Environment>
+	/// Custom op libraries must outlive the session using them.
+	#[cfg(feature = "custom-ops")]
+	_custom_runtime_handles: Vec<Arc<LibHandle>>,
+	_environment: Arc<Environment>,
+	log_id: Option<String>,
+	pub(crate) validate_input_types: bool,
+	pub(crate) validate_output_types: bool,
+	/// Initializer overrides must outlive the session using them.
+	_initializers: Vec<Arc<dyn Any>>
+}
+
+impl SharedSessionInner {
+	/// Returns the identifier set with [`SessionBuilder::with_log_id`], if any.
+	pub fn log_id(&self) -> Option<&str> {
+		self.log_id.as_deref()
+	}
+
+	/// Prefixes an error from running the session with the session's log ID, if it has one.
+	pub(crate) fn label_run_error(&self, error: ErrorInternal) -> ErrorInternal {
+		match (&self.log_id, error) {
+			(Some(log_id), ErrorInternal::Msg(msg)) => ErrorInternal::Msg(format!("[{log_id}] {msg}")),
+			(_, error) => error
+		}
+	}
 }
 
 unsafe impl Send for SharedSessionInner {}
@@ -536,10 +919,12 @@ impl Drop for SharedSessionInner {
 #[derive(Debug)]
 pub struct Session {
 	pub(crate) inner: Arc<SharedSessionInner>,
-	/// Information about the ONNX's inputs as stored in loaded file
+	/// Information about the ONNX's inputs as stored in loaded file; queried once when the session is created.
 	pub inputs: Vec<Input>,
-	/// Information about the ONNX's outputs as stored in loaded file
-	pub outputs: Vec<Output>
+	/// Information about the ONNX's outputs as stored in loaded file; queried once when the session is created.
+	pub outputs: Vec<Output>,
+	/// Names of the model's overridable initializers; queried once when the session is created.
+	overridable_initializers: Vec<String>
 }
 
 /// A [`Session`] with data stored in-memory.
@@ -557,28 +942,58 @@ impl<'s> Deref for InMemorySession<'s> {
 
 /// Information about an ONNX's input as stored in loaded file
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Input {
 	/// Name of the input layer
 	pub name: String,
 	/// Type of the input layer's elements
-	pub input_type: ValueType
+	pub input_type: ValueType,
+	/// The input's type denotation, e.g. `IMAGE` or `TEXT`, if the model declares one. See [`Input::denotation`].
+	pub denotation: Option<String>
+}
+
+impl Input {
+	/// Returns the input's [type denotation](https://github.com/onnx/onnx/blob/main/docs/TypeDenotation.md), e.g.
+	/// `IMAGE`, `AUDIO` or `TEXT`, if the model declares one.
+	///
+	/// Note that ONNX Runtime only exposes the denotation of the type as a whole; the denotations of individual
+	/// dimensions (like `DATA_BATCH` or `DATA_CHANNEL`) are not available through its API.
+	pub fn denotation(&self) -> Option<&str> {
+		self.denotation.as_deref()
+	}
+}
+
+impl Output {
+	/// Returns the output's [type denotation](https://github.com/onnx/onnx/blob/main/docs/TypeDenotation.md), if the
+	/// model declares one. See [`Input::denotation`].
+	pub fn denotation(&self) -> Option<&str> {
+		self.denotation.as_deref()
+	}
 }
 
 /// Information about an ONNX's output as stored in loaded file
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Output {
 	/// Name of the output layer
 	pub name: String,
 	/// Type of the output layer's elements
-	pub output_type: ValueType
+	pub output_type: ValueType,
+	/// The output's type denotation, e.g. `IMAGE` or `TEXT`, if the model declares one.
+	pub denotation: Option<String>
 }
 
 /// ONNX Run Options which is used to terminate/unterminate run(s) in a session
+///
+/// [`RunOptions`] owns the underlying `OrtRunOptions` and is deliberately not [`Clone`]; terminating a run only
+/// affects runs using the same options, so share one instance between threads with an [`Arc`] instead.
 #[derive(Debug)]
 pub struct RunOptions {
 	pub(crate) run_options_ptr: *mut ort_sys::OrtRunOptions
 }
 
+// `Run` only reads the `OrtRunOptions`, and `RunOptionsSetTerminate`/`RunOptionsUnsetTerminate` are documented as
+// callable from another thread while a run using them is in progress; the terminate flag is an atomic.
 // https://onnxruntime.ai/docs/api/c/struct_ort_api.html#ac2a08cac0a657604bd5899e0d1a13675
 unsafe impl Send for RunOptions {}
 unsafe impl Sync for RunOptions {}
@@ -592,16 +1007,61 @@ impl RunOptions {
 	}
 
 	/// Terminates the runs associated with [`RunOptions`].
+	///
+	/// The flag stays set until cleared, so any later run with these options will immediately fail as well. To reuse the
+	/// options after a terminated run, call [`RunOptions::set_unterminate`]:
+	///
+	/// ```no_run
+	/// # use std::sync::Arc;
+	/// # use ndarray::Array1;
+	/// # use ort::{RunOptions, Session};
+	/// # fn main() -> ort::Result<()> {
+	/// # let session = Session::builder()?.with_model_from_file("model.onnx")?;
+	/// let run_options = Arc::new(RunOptions::new()?);
+	///
+	/// // e.g. from another thread, while a run is in progress
+	/// run_options.set_terminate()?;
+	/// assert!(session.run_with_options(ort::inputs![Array1::<f32>::zeros(4)]?, Arc::clone(&run_options)).is_err());
+	///
+	/// run_options.set_unterminate()?;
+	/// let outputs = session.run_with_options(ort::inputs![Array1::<f32>::zeros(4)]?, Arc::clone(&run_options))?;
+	/// # Ok(())
+	/// # }
+	/// ```
 	pub fn set_terminate(&self) -> Result<()> {
 		ortsys![unsafe RunOptionsSetTerminate(self.run_options_ptr) -> Error::RunOptionsSetTerminate];
 		Ok(())
 	}
 
-	/// Unterminates the runs associated with [`RunOptions`].
+	/// Unterminates the runs associated with [`RunOptions`], clearing the flag set by [`RunOptions::set_terminate`] so
+	/// that the options can be used for new runs.
 	pub fn set_unterminate(&self) -> Result<()> {
 		ortsys![unsafe RunOptionsUnsetTerminate(self.run_options_ptr) -> Error::RunOptionsUnsetTerminate];
 		Ok(())
 	}
+
+	/// Shrinks the memory arenas of the given devices at the end of runs associated with [`RunOptions`], releasing
+	/// unused arena memory back to the system. This is useful for long-running services where a large batch would
+	/// otherwise leave the arena permanently grown.
+	///
+	/// `memory_info_spec` is a `;`-separated list of `device:device_id` pairs, e.g. `cpu:0` or `cpu:0;gpu:0`. Only
+	/// devices whose allocator is an arena are affected.
+	pub fn enable_arena_shrinkage(&self, memory_info_spec: &str) -> Result<()> {
+		self.add_config_entry("memory.enable_memory_arena_shrinkage", memory_info_spec)
+	}
+
+	fn add_config_entry(&self, key: &str, value: &str) -> Result<()> {
+		let key = CString::new(key)?;
+		let value = CString::new(value)?;
+		ortsys![unsafe AddRunConfigEntry(self.run_options_ptr, key.as_ptr(), value.as_ptr()) -> Error::AddRunConfigEntry];
+		Ok(())
+	}
+}
+
+impl Default for RunOptions {
+	fn default() -> Self {
+		Self::new().expect("error creating run options")
+	}
 }
 
 impl Drop for RunOptions {
@@ -618,11 +1078,70 @@ impl Session {
 		SessionBuilder::new()
 	}
 
+	/// Returns the identifier set with [`SessionBuilder::with_log_id`], if any.
+	pub fn log_id(&self) -> Option<&str> {
+		self.inner.log_id()
+	}
+
 	/// Returns this session's [`Allocator`].
 	pub fn allocator(&self) -> &Allocator {
 		&self.inner.allocator
 	}
 
+	/// Returns information about the model's inputs. A loaded model's signature can't change, so this is read from ONNX
+	/// Runtime once when the session is created and is free to call per request.
+	pub fn input_metadata(&self) -> &[Input] {
+		&self.inputs
+	}
+
+	/// Returns information about the model's outputs; see [`Session::input_metadata`].
+	pub fn output_metadata(&self) -> &[Output] {
+		&self.outputs
+	}
+
+	/// Returns the names of the `i`th input's dimensions; `None` for fixed or unnamed dimensions.
+	pub(crate) fn input_symbolic_dimensions(&self, i: usize) -> Result<Vec<Option<String>>> {
+		dangerous::extract_symbolic_dimensions(api().SessionGetInputTypeInfo.unwrap(), self.inner.session_ptr, i as _)
+	}
+
+	/// Returns the names of the `i`th output's dimensions; `None` for fixed or unnamed dimensions.
+	pub(crate) fn output_symbolic_dimensions(&self, i: usize) -> Result<Vec<Option<String>>> {
+		dangerous::extract_symbolic_dimensions(api().SessionGetOutputTypeInfo.unwrap(), self.inner.session_ptr, i as _)
+	}
+
+	/// Returns the number of inputs of the model, queried from ONNX Runtime without reading the inputs' names or types.
+	pub fn input_count(&self) -> Result<usize> {
+		dangerous::extract_inputs_count(self.inner.session_ptr)
+	}
+
+	/// Returns the number of outputs of the model, queried from ONNX Runtime without reading the outputs' names or
+	/// types.
+	pub fn output_count(&self) -> Result<usize> {
+		dangerous::extract_outputs_count(self.inner.session_ptr)
+	}
+
+	/// Returns the names of the model's inputs, queried from ONNX Runtime without reading the inputs' types; i.e. to
+	/// build [`Names`](crate::Names).
+	pub fn input_names(&self) -> Result<Vec<String>> {
+		(0..self.input_count()?)
+			.map(|i| dangerous::extract_input_name(self.inner.session_ptr, self.inner.allocator.ptr, i as _))
+			.collect()
+	}
+
+	/// Returns the names of the model's outputs, queried from ONNX Runtime without reading the outputs' types.
+	pub fn output_names(&self) -> Result<Vec<String>> {
+		(0..self.output_count()?)
+			.map(|i| dangerous::extract_output_name(self.inner.session_ptr, self.inner.allocator.ptr, i as _))
+			.collect()
+	}
+
+	/// Returns the names of the model's initializers that can be overridden by passing a value for them as an input,
+	/// i.e. with [`Session::run_with_io_ref`](crate::Session::run_with_io_ref). These aren't listed in
+	/// [`Session::inputs`]. Like those, they are queried once when the session is created.
+	pub fn overridable_initializer_names(&self) -> &[String] {
+		&self.overridable_initializers
+	}
+
 	/// Creates a new [`IoBinding`] for this session.
 	pub fn create_binding(&self) -> Result<IoBinding> {
 		IoBinding::new(self)
@@ -637,16 +1156,45 @@ impl Session {
 	pub fn run<'s, 'i, const N: usize>(&'s self, input_values: impl Into<SessionInputs<'i, N>>) -> Result<SessionOutputs<'s>> {
 		match input_values.into() {
 			SessionInputs::ValueSlice(input_values) => {
-				let outputs = self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), input_values, None)?;
+				let outputs = self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), input_values, &self.all_output_names(), None)?;
 				Ok(outputs)
 			}
 			SessionInputs::ValueArray(input_values) => {
-				let outputs = self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), &input_values, None)?;
+				let outputs = self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), &input_values, &self.all_output_names(), None)?;
 				Ok(outputs)
 			}
 			SessionInputs::ValueMap(input_values) => {
 				let (input_names, values): (Vec<&'static str>, Vec<Value>) = input_values.into_iter().unzip();
-				self.run_inner(&input_names, &values, None)
+				self.run_inner(&input_names, &values, &self.all_output_names(), None)
+			}
+		}
+	}
+
+	/// Like [`Session::run`], but only computes the outputs named in `output_names`, in that order. ONNX Runtime prunes
+	/// the nodes that don't contribute to the requested outputs, so this saves compute on models with many outputs.
+	///
+	/// Returns [`Error::UnknownOutput`] if the model has no output with one of the given names.
+	pub fn run_subset<'s, 'i, const N: usize>(&'s self, input_values: impl Into<SessionInputs<'i, N>>, output_names: &[&str]) -> Result<SessionOutputs<'s>> {
+		let output_names = output_names
+			.iter()
+			.map(|&name| {
+				self.outputs
+					.iter()
+					.find(|output| output.name == name)
+					.map(|output| output.name.as_str())
+					.ok_or_else(|| Error::UnknownOutput(name.to_owned()))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		match input_values.into() {
+			SessionInputs::ValueSlice(input_values) => {
+				self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), input_values, &output_names, None)
+			}
+			SessionInputs::ValueArray(input_values) => {
+				self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), &input_values, &output_names, None)
+			}
+			SessionInputs::ValueMap(input_values) => {
+				let (input_names, values): (Vec<&'static str>, Vec<Value>) = input_values.into_iter().unzip();
+				self.run_inner(&input_names, &values, &output_names, None)
 			}
 		}
 	}
@@ -659,34 +1207,38 @@ impl Session {
 	) -> Result<SessionOutputs<'s>> {
 		match input_values.into() {
 			SessionInputs::ValueSlice(input_values) => {
-				let outputs = self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), input_values, Some(run_options))?;
+				let outputs = self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), input_values, &self.all_output_names(), Some(run_options))?;
 				Ok(outputs)
 			}
 			SessionInputs::ValueArray(input_values) => {
-				let outputs = self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), &input_values, Some(run_options))?;
+				let outputs = self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), &input_values, &self.all_output_names(), Some(run_options))?;
 				Ok(outputs)
 			}
 			SessionInputs::ValueMap(input_values) => {
 				let (input_names, values): (Vec<&'static str>, Vec<Value>) = input_values.into_iter().unzip();
-				self.run_inner(&input_names, &values, Some(run_options))
+				self.run_inner(&input_names, &values, &self.all_output_names(), Some(run_options))
 			}
 		}
 	}
 
-	fn run_inner(&self, input_names: &[&str], input_values: &[Value], run_options: Option<Arc<RunOptions>>) -> Result<SessionOutputs<'_>> {
-		let input_names_ptr: Vec<*const c_char> = input_names
-			.iter()
-			.map(|n| CString::new(*n).unwrap())
-			.map(|n| n.into_raw() as *const c_char)
-			.collect();
-		let output_names_ptr: Vec<*const c_char> = self
-			.outputs
-			.iter()
-			.map(|output| CString::new(output.name.as_str()).unwrap())
-			.map(|n| n.into_raw() as *const c_char)
-			.collect();
+	fn all_output_names(&self) -> Vec<&str> {
+		self.outputs.iter().map(|output| output.name.as_str()).collect()
+	}
 
-		let mut output_tensor_ptrs: Vec<*mut ort_sys::OrtValue> = vec![std::ptr::null_mut(); self.outputs.len()];
+	fn run_inner<'s>(
+		&'s self,
+		input_names: &[&str],
+		input_values: &[Value],
+		output_names: &[&'s str],
+		run_options: Option<Arc<RunOptions>>
+	) -> Result<SessionOutputs<'s>> {
+		// Keep the names owned for the duration of the call so they are freed on both the success and error paths.
+		let input_names: Vec<CString> = input_names.iter().map(|n| CString::new(*n)).collect::<Result<_, _>>()?;
+		let output_names_c: Vec<CString> = output_names.iter().map(|n| CString::new(*n)).collect::<Result<_, _>>()?;
+		let input_names_ptr: Vec<*const c_char> = input_names.iter().map(|n| n.as_ptr()).collect();
+		let output_names_ptr: Vec<*const c_char> = output_names_c.iter().map(|n| n.as_ptr()).collect();
+
+		let mut output_tensor_ptrs: Vec<*mut ort_sys::OrtValue> = vec![std::ptr::null_mut(); output_names.len()];
 
 		// The C API expects pointers for the arrays (pointers to C-arrays)
 		let input_ort_values: Vec<*const ort_sys::OrtValue> = input_values.iter().map(|input_array_ort| input_array_ort.ptr() as *const _).collect();
@@ -707,7 +1259,7 @@ impl Session {
 				output_names_ptr.as_ptr(),
 				output_names_ptr.len() as _,
 				output_tensor_ptrs.as_mut_ptr()
-			) -> Error::SessionRun
+			) -> |e| Error::SessionRun(self.inner.label_run_error(e))
 		];
 
 		let outputs: Vec<Value> = output_tensor_ptrs
@@ -715,19 +1267,7 @@ impl Session {
 			.map(|tensor_ptr| unsafe { Value::from_raw(tensor_ptr, Arc::clone(&self.inner)) })
 			.collect();
 
-		// Reconvert name ptrs to CString so drop impl is called and memory is freed
-		drop(
-			input_names_ptr
-				.into_iter()
-				.chain(output_names_ptr.into_iter())
-				.map(|p| {
-					assert_non_null_pointer(p, "c_char for CString")?;
-					unsafe { Ok(CString::from_raw(p as *mut c_char)) }
-				})
-				.collect::<Result<Vec<_>>>()?
-		);
-
-		Ok(SessionOutputs::new(self.outputs.iter().map(|o| o.name.as_str()), outputs))
+		Ok(SessionOutputs::new(output_names.iter().copied(), outputs))
 	}
 
 	/// Gets the session model metadata. See [`ModelMetadata`] for more info.
@@ -755,6 +1295,24 @@ impl Session {
 unsafe impl Send for Session {}
 unsafe impl Sync for Session {}
 
+/// A handle to a custom op library loaded via `RegisterCustomOpsLibrary`; the library is unloaded when dropped.
+#[cfg(feature = "custom-ops")]
+#[derive(Debug)]
+struct LibHandle(*mut std::os::raw::c_void);
+
+// library handles may be closed from any thread
+#[cfg(feature = "custom-ops")]
+unsafe impl Send for LibHandle {}
+#[cfg(feature = "custom-ops")]
+unsafe impl Sync for LibHandle {}
+
+#[cfg(feature = "custom-ops")]
+impl Drop for LibHandle {
+	fn drop(&mut self) {
+		close_lib_handle(self.0);
+	}
+}
+
 #[cfg(all(unix, feature = "custom-ops"))]
 fn close_lib_handle(handle: *mut std::os::raw::c_void) {
 	unsafe { libc::dlclose(handle) };
@@ -770,10 +1328,7 @@ fn close_lib_handle(handle: *mut std::os::raw::c_void) {
 /// `SessionBuilder::with_model_from_file()` method.
 mod dangerous {
 	use super::*;
-	use crate::{
-		ortfree,
-		value::{extract_data_type_from_map_info, extract_data_type_from_sequence_info, extract_data_type_from_tensor_info}
-	};
+	use crate::{ortfree, value::extract_data_type_from_type_info};
 
 	pub(super) fn extract_inputs_count(session_ptr: *mut ort_sys::OrtSession) -> Result<usize> {
 		let f = api().SessionGetInputCount.unwrap();
@@ -799,16 +1354,32 @@ mod dangerous {
 		Ok(num_nodes as _)
 	}
 
-	fn extract_input_name(session_ptr: *mut ort_sys::OrtSession, allocator_ptr: *mut ort_sys::OrtAllocator, i: ort_sys::size_t) -> Result<String> {
+	pub(super) fn extract_input_name(session_ptr: *mut ort_sys::OrtSession, allocator_ptr: *mut ort_sys::OrtAllocator, i: ort_sys::size_t) -> Result<String> {
 		let f = api().SessionGetInputName.unwrap();
 		extract_io_name(f, session_ptr, allocator_ptr, i)
 	}
 
-	fn extract_output_name(session_ptr: *mut ort_sys::OrtSession, allocator_ptr: *mut ort_sys::OrtAllocator, i: ort_sys::size_t) -> Result<String> {
+	pub(super) fn extract_output_name(session_ptr: *mut ort_sys::OrtSession, allocator_ptr: *mut ort_sys::OrtAllocator, i: ort_sys::size_t) -> Result<String> {
 		let f = api().SessionGetOutputName.unwrap();
 		extract_io_name(f, session_ptr, allocator_ptr, i)
 	}
 
+	/// Unlike the input & output counts, which must be nonzero, a model may have no overridable initializers.
+	pub(super) fn extract_overridable_initializers_count(session_ptr: *mut ort_sys::OrtSession) -> Result<usize> {
+		let mut count = 0;
+		ortsys![unsafe SessionGetOverridableInitializerCount(session_ptr, &mut count) -> Error::GetInOutCount];
+		Ok(count as _)
+	}
+
+	pub(super) fn extract_overridable_initializer_name(
+		session_ptr: *mut ort_sys::OrtSession,
+		allocator_ptr: *mut ort_sys::OrtAllocator,
+		i: ort_sys::size_t
+	) -> Result<String> {
+		let f = api().SessionGetOverridableInitializerName.unwrap();
+		extract_io_name(f, session_ptr, allocator_ptr, i)
+	}
+
 	pub(crate) fn raw_pointer_to_string(allocator_ptr: *mut ort_sys::OrtAllocator, c_str: *mut c_char) -> Result<String> {
 		let name = match char_p_to_string(c_str) {
 			Ok(name) => name,
@@ -844,15 +1415,23 @@ mod dangerous {
 	pub(super) fn extract_input(session_ptr: *mut ort_sys::OrtSession, allocator_ptr: *mut ort_sys::OrtAllocator, i: usize) -> Result<Input> {
 		let input_name = extract_input_name(session_ptr, allocator_ptr, i as _)?;
 		let f = api().SessionGetInputTypeInfo.unwrap();
-		let input_type = extract_io(f, session_ptr, i as _)?;
-		Ok(Input { name: input_name, input_type })
+		let (input_type, denotation) = extract_io(f, session_ptr, i as _)?;
+		Ok(Input {
+			name: input_name,
+			input_type,
+			denotation
+		})
 	}
 
 	pub(super) fn extract_output(session_ptr: *mut ort_sys::OrtSession, allocator_ptr: *mut ort_sys::OrtAllocator, i: usize) -> Result<Output> {
 		let output_name = extract_output_name(session_ptr, allocator_ptr, i as _)?;
 		let f = api().SessionGetOutputTypeInfo.unwrap();
-		let output_type = extract_io(f, session_ptr, i as _)?;
-		Ok(Output { name: output_name, output_type })
+		let (output_type, denotation) = extract_io(f, session_ptr, i as _)?;
+		Ok(Output {
+			name: output_name,
+			output_type,
+			denotation
+		})
 	}
 
 	fn extract_io(
@@ -863,36 +1442,77 @@ mod dangerous {
 		) -> *mut ort_sys::OrtStatus },
 		session_ptr: *mut ort_sys::OrtSession,
 		i: ort_sys::size_t
-	) -> Result<ValueType> {
+	) -> Result<(ValueType, Option<String>)> {
 		let mut typeinfo_ptr: *mut ort_sys::OrtTypeInfo = std::ptr::null_mut();
 
 		let status = unsafe { f(session_ptr, i, &mut typeinfo_ptr) };
 		status_to_result(status).map_err(Error::GetTypeInfo)?;
 		assert_non_null_pointer(typeinfo_ptr, "TypeInfo")?;
 
-		let mut ty: ort_sys::ONNXType = ort_sys::ONNXType::ONNX_TYPE_UNKNOWN;
-		let status = ortsys![unsafe GetOnnxTypeFromTypeInfo(typeinfo_ptr, &mut ty)];
-		status_to_result(status).map_err(Error::GetOnnxTypeFromTypeInfo)?;
-		let io_type = match ty {
-			ort_sys::ONNXType::ONNX_TYPE_TENSOR | ort_sys::ONNXType::ONNX_TYPE_SPARSETENSOR => {
-				let mut info_ptr: *const ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToTensorInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(info_ptr)];
-				unsafe { extract_data_type_from_tensor_info(info_ptr)? }
-			}
-			ort_sys::ONNXType::ONNX_TYPE_SEQUENCE => {
-				let mut info_ptr: *const ort_sys::OrtSequenceTypeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToSequenceTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToSequenceTypeInfo; nonNull(info_ptr)];
-				unsafe { extract_data_type_from_sequence_info(info_ptr)? }
-			}
-			ort_sys::ONNXType::ONNX_TYPE_MAP => {
-				let mut info_ptr: *const ort_sys::OrtMapTypeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToMapTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToMapTypeInfo; nonNull(info_ptr)];
-				unsafe { extract_data_type_from_map_info(info_ptr)? }
-			}
-			_ => unreachable!()
-		};
+		let io_type = unsafe { extract_data_type_from_type_info(typeinfo_ptr) }.and_then(|io_type| Ok((io_type, extract_denotation(typeinfo_ptr)?)));
+		ortsys![unsafe ReleaseTypeInfo(typeinfo_ptr)];
+		io_type
+	}
+
+	/// Extracts the names of the dimensions of a tensor input or output, i.e. `batch` for a dimension declared as
+	/// `batch`; `None` for fixed or unnamed dimensions. Returns an empty `Vec` if the input or output isn't a tensor.
+	pub(super) fn extract_symbolic_dimensions(
+		f: extern_system_fn! { unsafe fn(
+			*const ort_sys::OrtSession,
+			ort_sys::size_t,
+			*mut *mut ort_sys::OrtTypeInfo,
+		) -> *mut ort_sys::OrtStatus },
+		session_ptr: *mut ort_sys::OrtSession,
+		i: ort_sys::size_t
+	) -> Result<Vec<Option<String>>> {
+		let mut typeinfo_ptr: *mut ort_sys::OrtTypeInfo = std::ptr::null_mut();
+
+		let status = unsafe { f(session_ptr, i, &mut typeinfo_ptr) };
+		status_to_result(status).map_err(Error::GetTypeInfo)?;
+		assert_non_null_pointer(typeinfo_ptr, "TypeInfo")?;
 
+		let dimensions = extract_symbolic_dimensions_from_type_info(typeinfo_ptr);
 		ortsys![unsafe ReleaseTypeInfo(typeinfo_ptr)];
-		Ok(io_type)
+		dimensions
+	}
+
+	/// Copies the symbolic dimension names out of a type info, since ONNX Runtime's are only valid for its lifetime.
+	fn extract_symbolic_dimensions_from_type_info(typeinfo_ptr: *const ort_sys::OrtTypeInfo) -> Result<Vec<Option<String>>> {
+		let mut ty: ort_sys::ONNXType = ort_sys::ONNXType::ONNX_TYPE_UNKNOWN;
+		ortsys![unsafe GetOnnxTypeFromTypeInfo(typeinfo_ptr, &mut ty) -> Error::GetOnnxTypeFromTypeInfo];
+		if ty != ort_sys::ONNXType::ONNX_TYPE_TENSOR {
+			return Ok(Vec::new());
+		}
+
+		let mut info_ptr: *const ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
+		ortsys![unsafe CastTypeInfoToTensorInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(info_ptr)];
+		let mut num_dims = 0;
+		ortsys![unsafe GetDimensionsCount(info_ptr, &mut num_dims) -> Error::GetDimensionsCount];
+		let mut dim_params: Vec<*const c_char> = vec![std::ptr::null(); num_dims as _];
+		ortsys![unsafe GetSymbolicDimensions(info_ptr, dim_params.as_mut_ptr(), num_dims as _) -> Error::GetDimensions];
+
+		Ok(dim_params
+			.into_iter()
+			.map(|param| {
+				if param.is_null() {
+					return None;
+				}
+				let param = unsafe { std::ffi::CStr::from_ptr(param) }.to_string_lossy();
+				(!param.is_empty()).then(|| param.into_owned())
+			})
+			.collect())
+	}
+
+	/// Extracts the type denotation of a type info, if it has one. The returned string is copied, since ONNX Runtime's
+	/// is only valid for the lifetime of the type info.
+	fn extract_denotation(typeinfo_ptr: *const ort_sys::OrtTypeInfo) -> Result<Option<String>> {
+		let mut denotation_ptr: *const c_char = std::ptr::null();
+		let mut len: ort_sys::size_t = 0;
+		ortsys![unsafe GetDenotationFromTypeInfo(typeinfo_ptr, &mut denotation_ptr, &mut len) -> Error::GetTypeInfo];
+		if denotation_ptr.is_null() || len == 0 {
+			return Ok(None);
+		}
+		let bytes = unsafe { std::slice::from_raw_parts(denotation_ptr as *const u8, len as _) };
+		Ok(Some(String::from_utf8_lossy(bytes).into_owned()))
 	}
 }
@@ -0,0 +1,300 @@
+//! Sparse tensor support.
+//!
+//! ONNX Runtime represents sparse tensors as a values buffer plus one or more index buffers describing where each value
+//! lives in the dense tensor. [`SparseTensor`] wraps Rust-owned values & indices without copying them, similar to
+//! [`RustOwnerValue`](crate::RustOwnerValue).
+
+use std::{fmt::Debug, ops::Deref, ptr};
+
+use crate::{
+	error::assert_non_null_pointer,
+	memory::MemoryInfo,
+	ortsys,
+	run::RunError,
+	tensor::{IntoTensorElementType, TensorElementType},
+	value::Value,
+	AllocatorType, Error, MemType, Result
+};
+
+/// The storage format of a sparse tensor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SparseFormat {
+	/// Coordinate format; each value has either a linear index into the dense tensor, or one index per dense dimension.
+	Coo,
+	/// Compressed sparse row format, using inner (column) and outer (row offset) indices. Only valid for 2D tensors.
+	Csr,
+	/// Block sparse format.
+	BlockSparse
+}
+
+impl TryFrom<ort_sys::OrtSparseFormat> for SparseFormat {
+	type Error = Error;
+
+	fn try_from(value: ort_sys::OrtSparseFormat) -> Result<Self> {
+		match value {
+			ort_sys::OrtSparseFormat::ORT_SPARSE_COO => Ok(SparseFormat::Coo),
+			ort_sys::OrtSparseFormat::ORT_SPARSE_CSRC => Ok(SparseFormat::Csr),
+			ort_sys::OrtSparseFormat::ORT_SPARSE_BLOCK_SPARSE => Ok(SparseFormat::BlockSparse),
+			ort_sys::OrtSparseFormat::ORT_SPARSE_UNDEFINED => Err(Error::NotSparseTensor)
+		}
+	}
+}
+
+/// Identifies one of the index buffers of a sparse tensor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SparseIndicesFormat {
+	/// Indices of a [`SparseFormat::Coo`] tensor.
+	Coo,
+	/// Inner (column) indices of a [`SparseFormat::Csr`] tensor.
+	CsrInner,
+	/// Outer (row offset) indices of a [`SparseFormat::Csr`] tensor.
+	CsrOuter,
+	/// Indices of a [`SparseFormat::BlockSparse`] tensor.
+	BlockSparse
+}
+
+impl From<SparseIndicesFormat> for ort_sys::OrtSparseIndicesFormat {
+	fn from(value: SparseIndicesFormat) -> Self {
+		match value {
+			SparseIndicesFormat::Coo => ort_sys::OrtSparseIndicesFormat::ORT_SPARSE_COO_INDICES,
+			SparseIndicesFormat::CsrInner => ort_sys::OrtSparseIndicesFormat::ORT_SPARSE_CSR_INNER_INDICES,
+			SparseIndicesFormat::CsrOuter => ort_sys::OrtSparseIndicesFormat::ORT_SPARSE_CSR_OUTER_INDICES,
+			SparseIndicesFormat::BlockSparse => ort_sys::OrtSparseIndicesFormat::ORT_SPARSE_BLOCK_SPARSE_INDICES
+		}
+	}
+}
+
+/// A sparse tensor backed by Rust-owned values and indices.
+///
+/// Neither buffer is copied; ONNX Runtime reads directly from `values` and `indices`, so both are held for as long as
+/// the tensor lives. Use [`Value::from`] to pass a sparse tensor to [`Session::run`](crate::Session::run).
+pub struct SparseTensor<Values, Indices> {
+	ptr: *mut ort_sys::OrtValue,
+	values: Values,
+	indices: Vec<Indices>,
+	memory_info: MemoryInfo
+}
+
+impl<Values, Indices> Drop for SparseTensor<Values, Indices> {
+	fn drop(&mut self) {
+		if !self.ptr.is_null() {
+			ortsys![unsafe ReleaseValue(self.ptr)];
+		}
+		self.ptr = ptr::null_mut();
+	}
+}
+
+impl<Values, Indices, T> SparseTensor<Values, Indices>
+where
+	Values: Deref<Target = [T]>,
+	Indices: Deref<Target = [i64]>,
+	T: IntoTensorElementType + Debug + Clone + 'static
+{
+	fn with_values(dense_shape: &[i64], values: Values) -> Result<(*mut ort_sys::OrtValue, Values, MemoryInfo), RunError> {
+		let memory_info = MemoryInfo::new_cpu_shared(AllocatorType::Arena, MemType::Default)?;
+		let values_shape = [values.len() as i64];
+		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+		ortsys![
+			unsafe CreateSparseTensorWithValuesAsOrtValue(
+				memory_info.ptr,
+				values.as_ptr() as *mut std::ffi::c_void,
+				dense_shape.as_ptr(),
+				dense_shape.len() as _,
+				values_shape.as_ptr(),
+				values_shape.len() as _,
+				T::into_tensor_element_type().into(),
+				&mut value_ptr
+			) -> Error::CreateSparseTensor;
+			nonNull(value_ptr)
+		];
+		Ok((value_ptr, values, memory_info))
+	}
+
+	/// Creates a new COO-format sparse tensor.
+	///
+	/// `indices` must contain either one linear index into the dense tensor per value, or `dense_shape.len()` indices
+	/// per value (one for each dimension).
+	pub fn new_coo(dense_shape: &[i64], values: Values, indices: Indices) -> Result<Self, RunError> {
+		if indices.len() != values.len() && indices.len() != values.len() * dense_shape.len() {
+			return Err(RunError::Msg(format!(
+				"COO indices len should be {} (linear) or {} (per dimension), got {}",
+				values.len(),
+				values.len() * dense_shape.len(),
+				indices.len()
+			)));
+		}
+		let (ptr, values, memory_info) = Self::with_values(dense_shape, values)?;
+		// construct `Self` first so the value is released if setting the indices fails
+		let mut tensor = Self {
+			ptr,
+			values,
+			indices: Vec::with_capacity(1),
+			memory_info
+		};
+		ortsys![unsafe UseCooIndices(tensor.ptr, indices.as_ptr() as *mut i64, indices.len() as _) -> Error::CreateSparseTensor];
+		tensor.indices.push(indices);
+		Ok(tensor)
+	}
+
+	/// Creates a new CSR-format sparse tensor. `dense_shape` must be 2-dimensional.
+	///
+	/// `inner_indices` holds the column index of each value, and `outer_indices` holds `rows + 1` offsets into
+	/// `inner_indices` marking where each row begins.
+	pub fn new_csr(dense_shape: &[i64], values: Values, inner_indices: Indices, outer_indices: Indices) -> Result<Self, RunError> {
+		if dense_shape.len() != 2 {
+			return Err(RunError::Msg(format!("CSR sparse tensors must be 2-dimensional, got shape {dense_shape:?}")));
+		}
+		if dense_shape.iter().any(|&dim| dim < 0) {
+			return Err(Error::InvalidShape(dense_shape.to_vec()).into());
+		}
+		if inner_indices.len() != values.len() {
+			return Err(RunError::Msg(format!("CSR inner indices len should be == values len: [{} == {}]?", inner_indices.len(), values.len())));
+		}
+		if outer_indices.len() != dense_shape[0] as usize + 1 {
+			return Err(RunError::Msg(format!("CSR outer indices len should be == rows + 1: [{} == {}]?", outer_indices.len(), dense_shape[0] + 1)));
+		}
+		let (ptr, values, memory_info) = Self::with_values(dense_shape, values)?;
+		let mut tensor = Self {
+			ptr,
+			values,
+			indices: Vec::with_capacity(2),
+			memory_info
+		};
+		ortsys![
+			unsafe UseCsrIndices(
+				tensor.ptr,
+				inner_indices.as_ptr() as *mut i64,
+				inner_indices.len() as _,
+				outer_indices.as_ptr() as *mut i64,
+				outer_indices.len() as _
+			) -> Error::CreateSparseTensor
+		];
+		tensor.indices.push(inner_indices);
+		tensor.indices.push(outer_indices);
+		Ok(tensor)
+	}
+
+	/// Returns the Rust-owned values of this sparse tensor.
+	#[inline]
+	pub fn values(&self) -> &[T] {
+		&self.values
+	}
+}
+
+impl<Values, Indices> SparseTensor<Values, Indices> {
+	/// Returns the storage format of this sparse tensor.
+	pub fn format(&self) -> Result<SparseFormat> {
+		sparse_format(self.ptr)
+	}
+
+	/// Returns the index buffer identified by `format`, as seen by ONNX Runtime.
+	pub fn indices(&self, format: SparseIndicesFormat) -> Result<&[i64]> {
+		unsafe { sparse_indices(self.ptr, format) }
+	}
+
+	#[inline]
+	pub fn ptr(&self) -> *const ort_sys::OrtValue {
+		self.ptr as _
+	}
+}
+
+impl<Values: 'static, Indices: 'static> From<SparseTensor<Values, Indices>> for Value {
+	fn from(tensor: SparseTensor<Values, Indices>) -> Self {
+		let tensor = std::mem::ManuallyDrop::new(tensor);
+		// `tensor` is never dropped, so each field is moved out exactly once here
+		let (ptr, values, indices, memory_info) =
+			unsafe { (tensor.ptr, ptr::read(&tensor.values), ptr::read(&tensor.indices), ptr::read(&tensor.memory_info)) };
+		unsafe { Value::from_raw_rust_owned(ptr, Box::new((values, indices)), memory_info) }
+	}
+}
+
+pub(crate) fn sparse_format(ptr: *mut ort_sys::OrtValue) -> Result<SparseFormat> {
+	let mut format = ort_sys::OrtSparseFormat::ORT_SPARSE_UNDEFINED;
+	ortsys![unsafe GetSparseTensorFormat(ptr, &mut format) -> Error::GetSparseTensorFormat];
+	format.try_into()
+}
+
+pub(crate) unsafe fn sparse_values<'v, T: IntoTensorElementType>(ptr: *mut ort_sys::OrtValue) -> Result<(Vec<i64>, &'v [T])> {
+	let mut info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = ptr::null_mut();
+	ortsys![GetSparseTensorValuesTypeAndShape(ptr, &mut info_ptr) -> Error::GetSparseTensorValues; nonNull(info_ptr)];
+
+	let res = (|| {
+		let mut type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
+		ortsys![GetTensorElementType(info_ptr, &mut type_sys) -> Error::GetTensorElementType];
+		if type_sys == ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED {
+			return Err(Error::UndefinedTensorElementType);
+		}
+		let data_type: TensorElementType = type_sys.into();
+		if data_type != T::into_tensor_element_type() {
+			return Err(Error::DataTypeMismatch {
+				actual: data_type,
+				requested: T::into_tensor_element_type()
+			});
+		}
+
+		let mut num_dims = 0;
+		ortsys![GetDimensionsCount(info_ptr, &mut num_dims) -> Error::GetDimensionsCount];
+		let mut values_shape: Vec<i64> = vec![0; num_dims as _];
+		ortsys![GetDimensions(info_ptr, values_shape.as_mut_ptr(), num_dims as _) -> Error::GetDimensions];
+
+		let mut len = 0;
+		ortsys![GetTensorShapeElementCount(info_ptr, &mut len) -> Error::GetTensorShapeElementCount];
+		if len == 0 {
+			return Ok((values_shape, &[][..]));
+		}
+
+		let mut values_ptr: *const std::ffi::c_void = ptr::null();
+		ortsys![GetSparseTensorValues(ptr, &mut values_ptr) -> Error::GetSparseTensorValues];
+		assert_non_null_pointer(values_ptr, "SparseTensorValues")?;
+		Ok((values_shape, std::slice::from_raw_parts(values_ptr as *const T, len as _)))
+	})();
+	ortsys![ReleaseTensorTypeAndShapeInfo(info_ptr)];
+	res
+}
+
+pub(crate) unsafe fn sparse_indices<'v>(ptr: *mut ort_sys::OrtValue, format: SparseIndicesFormat) -> Result<&'v [i64]> {
+	let mut num_indices = 0;
+	let mut indices_ptr: *const std::ffi::c_void = ptr::null();
+	ortsys![GetSparseTensorIndices(ptr, format.into(), &mut num_indices, &mut indices_ptr) -> Error::GetSparseTensorIndices];
+	if num_indices == 0 {
+		return Ok(&[]);
+	}
+	assert_non_null_pointer(indices_ptr, "SparseTensorIndices")?;
+	Ok(std::slice::from_raw_parts(indices_ptr as *const i64, num_indices as _))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_sparse_coo() -> crate::Result<()> {
+		let values: Vec<f32> = vec![1., 2., 3.];
+		let indices: Vec<i64> = vec![0, 5, 11];
+		let tensor = SparseTensor::new_coo(&[3, 4], values.clone(), indices.clone()).unwrap();
+		assert_eq!(tensor.format()?, SparseFormat::Coo);
+		assert_eq!(tensor.indices(SparseIndicesFormat::Coo)?, &indices);
+
+		let value = Value::from(tensor);
+		assert!(value.is_sparse_tensor()?);
+		let (values_shape, extracted) = value.extract_sparse_values::<f32>()?;
+		assert_eq!(values_shape, vec![3]);
+		assert_eq!(extracted, &values);
+		assert_eq!(value.extract_sparse_indices(SparseIndicesFormat::Coo)?, &indices);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_sparse_coo_bad_indices() {
+		assert!(SparseTensor::new_coo(&[3, 4], vec![1.0f32, 2.0], vec![0i64, 1, 2]).is_err());
+	}
+
+	#[test]
+	fn test_sparse_csr_negative_dimension() {
+		for rows in [-1, -2] {
+			let tensor = SparseTensor::new_csr(&[rows, 4], vec![1.0f32], vec![0i64], vec![0i64, 1]);
+			assert!(matches!(tensor, Err(RunError::OrtError(Error::InvalidShape(_)))));
+		}
+	}
+}
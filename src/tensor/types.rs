@@ -1,12 +1,14 @@
 use std::fmt::{self, Debug};
 #[cfg(feature = "ndarray")]
-use std::{ffi, ptr, result, string};
+use std::{ffi, result, string};
 
 #[cfg(feature = "ndarray")]
 use super::{ortsys, Error, Result};
 
 /// Enum mapping ONNX Runtime's supported tensor data types.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum TensorElementType {
 	/// 32-bit floating point number, equivalent to Rust's `f32`.
 	Float32,
@@ -47,6 +49,63 @@ pub enum TensorElementType {
 	Bfloat16
 }
 
+impl TensorElementType {
+	/// Returns the size in bytes of a single element, or `None` for [`TensorElementType::String`], whose elements
+	/// have no fixed size.
+	pub fn size(self) -> Option<usize> {
+		crate::get_type_size(self.into()).ok()
+	}
+
+	/// Returns `true` for the floating point types.
+	pub fn is_float(self) -> bool {
+		match self {
+			TensorElementType::Float32 | TensorElementType::Float64 => true,
+			#[cfg(feature = "half")]
+			TensorElementType::Float16 | TensorElementType::Bfloat16 => true,
+			_ => false
+		}
+	}
+
+	/// Returns `true` for the signed & unsigned integer types; [`TensorElementType::Bool`] is not an integer type.
+	pub fn is_integer(self) -> bool {
+		matches!(
+			self,
+			TensorElementType::Uint8
+				| TensorElementType::Int8
+				| TensorElementType::Uint16
+				| TensorElementType::Int16
+				| TensorElementType::Int32
+				| TensorElementType::Int64
+				| TensorElementType::Uint32
+				| TensorElementType::Uint64
+		)
+	}
+}
+
+/// Formats the type with the name ONNX uses for it, as in `tensor(float)`.
+impl fmt::Display for TensorElementType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			TensorElementType::Float32 => "float",
+			TensorElementType::Uint8 => "uint8",
+			TensorElementType::Int8 => "int8",
+			TensorElementType::Uint16 => "uint16",
+			TensorElementType::Int16 => "int16",
+			TensorElementType::Int32 => "int32",
+			TensorElementType::Int64 => "int64",
+			TensorElementType::String => "string",
+			TensorElementType::Bool => "bool",
+			#[cfg(feature = "half")]
+			TensorElementType::Float16 => "float16",
+			TensorElementType::Float64 => "double",
+			TensorElementType::Uint32 => "uint32",
+			TensorElementType::Uint64 => "uint64",
+			#[cfg(feature = "half")]
+			TensorElementType::Bfloat16 => "bfloat16"
+		})
+	}
+}
+
 impl From<TensorElementType> for ort_sys::ONNXTensorElementDataType {
 	fn from(val: TensorElementType) -> Self {
 		match val {
@@ -223,10 +282,7 @@ where
 	D: ndarray::Dimension
 {
 	// Get pointer to output tensor values
-	let mut output_array_ptr: *mut T = ptr::null_mut();
-	let output_array_ptr_ptr: *mut *mut T = &mut output_array_ptr;
-	let output_array_ptr_ptr_void: *mut *mut std::ffi::c_void = output_array_ptr_ptr as *mut *mut std::ffi::c_void;
-	ortsys![unsafe GetTensorMutableData(tensor, output_array_ptr_ptr_void) -> Error::GetTensorMutableData; nonNull(output_array_ptr)];
+	let output_array_ptr: *mut T = crate::run::unchecked_tensor_data_ptr(tensor, shape.size())?;
 
 	let array_view = unsafe { ndarray::ArrayView::from_shape_ptr(shape, output_array_ptr) }.into_dyn();
 	Ok(array_view)
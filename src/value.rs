@@ -9,26 +9,83 @@ use crate::{
 	error::{assert_non_null_pointer, status_to_result},
 	memory::{Allocator, MemoryInfo},
 	ortsys,
-	session::SharedSessionInner,
+	session::{Session, SharedSessionInner},
+	sparse::{self, SparseFormat, SparseIndicesFormat},
 	tensor::{ExtractTensorData, IntoTensorElementType, TensorElementType, Utf8Data},
 	AllocatorType, Error, MemType, Result
 };
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ValueType {
 	Tensor { ty: TensorElementType, dimensions: Vec<i64> },
 	Sequence(Box<ValueType>),
-	Map { key: TensorElementType, value: TensorElementType }
+	Map { key: TensorElementType, value: TensorElementType },
+	/// A value that may or may not be present, i.e. the `optional` input of a model exported from PyTorch.
+	Optional(Box<ValueType>)
 }
 
 impl ValueType {
-	/// Returns the dimensions of this data type if it is a tensor, or `None` if it is a sequence or map.
+	/// Returns the dimensions of this data type if it is a tensor, or `None` if it is a sequence, map, or optional.
 	pub fn tensor_dimensions(&self) -> Option<&Vec<i64>> {
 		match self {
 			ValueType::Tensor { dimensions, .. } => Some(dimensions),
 			_ => None
 		}
 	}
+
+	/// Returns `true` if a value of this type can be fed where `other` is expected, i.e. to check that one model's
+	/// output fits another model's input.
+	///
+	/// Tensors are compatible if their element types match and their dimensions are broadcast-compatible: aligned from
+	/// the last dimension, each pair of dimensions must be equal, or either must be `1` or symbolic (`-1`). Sequences
+	/// and optionals are compatible if their contained types are; maps must match exactly.
+	pub fn is_compatible_with(&self, other: &ValueType) -> bool {
+		match (self, other) {
+			(ValueType::Tensor { ty, dimensions }, ValueType::Tensor { ty: other_ty, dimensions: other_dimensions }) => {
+				ty == other_ty
+					&& dimensions
+						.iter()
+						.rev()
+						.zip(other_dimensions.iter().rev())
+						.all(|(&a, &b)| a == b || a == 1 || b == 1 || a == -1 || b == -1)
+			}
+			(ValueType::Sequence(inner), ValueType::Sequence(other_inner)) | (ValueType::Optional(inner), ValueType::Optional(other_inner)) => {
+				inner.is_compatible_with(other_inner)
+			}
+			(ValueType::Map { .. }, ValueType::Map { .. }) => self == other,
+			_ => false
+		}
+	}
+}
+
+/// The kind of an ONNX value, as returned by [`Value::value_type`]. Unlike [`ValueType`], this doesn't describe the
+/// value's element types or shape, so it is cheap to get and match on when consuming outputs of unknown kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OnnxType {
+	Tensor,
+	Sequence,
+	Map,
+	/// An opaque value of a custom type, i.e. produced by a custom operator.
+	Opaque,
+	SparseTensor,
+	Optional
+}
+
+impl TryFrom<ort_sys::ONNXType> for OnnxType {
+	type Error = Error;
+
+	fn try_from(value: ort_sys::ONNXType) -> Result<Self> {
+		match value {
+			ort_sys::ONNXType::ONNX_TYPE_TENSOR => Ok(OnnxType::Tensor),
+			ort_sys::ONNXType::ONNX_TYPE_SEQUENCE => Ok(OnnxType::Sequence),
+			ort_sys::ONNXType::ONNX_TYPE_MAP => Ok(OnnxType::Map),
+			ort_sys::ONNXType::ONNX_TYPE_OPAQUE => Ok(OnnxType::Opaque),
+			ort_sys::ONNXType::ONNX_TYPE_SPARSETENSOR => Ok(OnnxType::SparseTensor),
+			ort_sys::ONNXType::ONNX_TYPE_OPTIONAL => Ok(OnnxType::Optional),
+			ort_sys::ONNXType::ONNX_TYPE_UNKNOWN => Err(Error::GetValueType(crate::ErrorInternal::Msg("value has an unknown type".to_owned())))
+		}
+	}
 }
 
 #[doc(hidden)]
@@ -166,6 +223,23 @@ impl Value {
 		}
 	}
 
+	/// Construct a [`Value`] from a C++ [`ort_sys::OrtValue`] pointer whose backing data is owned by Rust.
+	///
+	/// # Safety
+	///
+	/// - `ptr` must not be null.
+	/// - `guard` must keep alive any memory `ptr` references, and `memory_info` must be the memory info it was created
+	///   with.
+	pub(crate) unsafe fn from_raw_rust_owned(ptr: *mut ort_sys::OrtValue, guard: Box<dyn Any>, memory_info: MemoryInfo) -> Value {
+		Value {
+			inner: ValueInner::RustOwned {
+				ptr,
+				_array: guard,
+				_memory_info: memory_info
+			}
+		}
+	}
+
 	pub fn tensor_element_type(&self) -> Result<TensorElementType> {
 		let mut tensor_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
 		ortsys![unsafe GetTensorTypeAndShape(self.ptr(), &mut tensor_info_ptr) -> Error::GetTensorTypeAndShape];
@@ -181,30 +255,9 @@ impl Value {
 		let mut typeinfo_ptr: *mut ort_sys::OrtTypeInfo = std::ptr::null_mut();
 		ortsys![unsafe GetTypeInfo(self.ptr(), &mut typeinfo_ptr) -> Error::GetTypeInfo; nonNull(typeinfo_ptr)];
 
-		let mut ty: ort_sys::ONNXType = ort_sys::ONNXType::ONNX_TYPE_UNKNOWN;
-		let status = ortsys![unsafe GetOnnxTypeFromTypeInfo(typeinfo_ptr, &mut ty)];
-		status_to_result(status).map_err(Error::GetOnnxTypeFromTypeInfo)?;
-		let io_type = match ty {
-			ort_sys::ONNXType::ONNX_TYPE_TENSOR | ort_sys::ONNXType::ONNX_TYPE_SPARSETENSOR => {
-				let mut info_ptr: *const ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToTensorInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(info_ptr)];
-				unsafe { extract_data_type_from_tensor_info(info_ptr)? }
-			}
-			ort_sys::ONNXType::ONNX_TYPE_SEQUENCE => {
-				let mut info_ptr: *const ort_sys::OrtSequenceTypeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToSequenceTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToSequenceTypeInfo; nonNull(info_ptr)];
-				unsafe { extract_data_type_from_sequence_info(info_ptr)? }
-			}
-			ort_sys::ONNXType::ONNX_TYPE_MAP => {
-				let mut info_ptr: *const ort_sys::OrtMapTypeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToMapTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToMapTypeInfo; nonNull(info_ptr)];
-				unsafe { extract_data_type_from_map_info(info_ptr)? }
-			}
-			_ => unreachable!()
-		};
-
+		let io_type = unsafe { extract_data_type_from_type_info(typeinfo_ptr) };
 		ortsys![unsafe ReleaseTypeInfo(typeinfo_ptr)];
-		Ok(io_type)
+		io_type
 	}
 
 	/// Attempt to extract the underlying data into a Rust `ndarray`.
@@ -288,13 +341,9 @@ impl Value {
 				ortsys![unsafe IsTensor(self.ptr(), &mut is_tensor) -> Error::FailedTensorCheck];
 				assert_eq!(is_tensor, 1);
 
-				let mut output_array_ptr: *mut T = ptr::null_mut();
-				let output_array_ptr_ptr: *mut *mut T = &mut output_array_ptr;
-				let output_array_ptr_ptr_void: *mut *mut std::ffi::c_void = output_array_ptr_ptr as *mut *mut std::ffi::c_void;
-				ortsys![unsafe GetTensorMutableData(self.ptr(), output_array_ptr_ptr_void) -> Error::GetTensorMutableData; nonNull(output_array_ptr)];
-
 				let mut len = 0;
 				ortsys![unsafe GetTensorShapeElementCount(tensor_info_ptr, &mut len) -> Error::GetTensorShapeElementCount];
+				let output_array_ptr: *mut T = crate::run::unchecked_tensor_data_ptr(self.ptr(), len as _)?;
 
 				Ok((node_dims, unsafe { std::slice::from_raw_parts(output_array_ptr, len as _) }))
 			}
@@ -303,7 +352,114 @@ impl Value {
 		res
 	}
 
+	/// Extracts the single element of a tensor containing exactly one element (i.e. a scalar, or a tensor of shape
+	/// `[1]`/`[1, 1]`), such as the score output by a regression model.
+	pub fn try_scalar<T>(&self) -> Result<T>
+	where
+		T: ExtractTensorData + Copy + Debug
+	{
+		let (_, data) = self.extract_raw_tensor::<T>()?;
+		match data {
+			[value] => Ok(*value),
+			_ => Err(Error::NotScalar(data.len()))
+		}
+	}
+
+	/// Dequantizes the output of an INT8-quantized model, computing `(x - zero_point) * scale` for each element of a
+	/// `Uint8` or `Int8` tensor, in row-major order.
+	pub fn dequantize_to_f32(&self, scale: f32, zero_point: i32) -> Result<Vec<f32>> {
+		let dequantize = |x: i32| (x - zero_point) as f32 * scale;
+		match self.tensor_element_type()? {
+			TensorElementType::Uint8 => Ok(self.extract_raw_tensor::<u8>()?.1.iter().map(|&x| dequantize(x.into())).collect()),
+			TensorElementType::Int8 => Ok(self.extract_raw_tensor::<i8>()?.1.iter().map(|&x| dequantize(x.into())).collect()),
+			ty => Err(Error::NotQuantized(ty))
+		}
+	}
+
+	/// Returns the index of the largest element along `axis` (negative values count from the last dimension) of an
+	/// `f32` or `f64` tensor, e.g. the predicted class of each row of a `[batch, num_classes]` classifier output with
+	/// `axis = -1`. The result holds one index for each position of the other dimensions, in row-major order; ties go
+	/// to the first index and `NaN`s are ignored.
+	pub fn argmax(&self, axis: i64) -> Result<Vec<i64>> {
+		match self.tensor_element_type()? {
+			TensorElementType::Float64 => {
+				let (shape, data) = self.extract_raw_tensor::<f64>()?;
+				argmax(&shape, data, axis)
+			}
+			_ => {
+				let (shape, data) = self.extract_raw_tensor::<f32>()?;
+				argmax(&shape, data, axis)
+			}
+		}
+	}
+
+	/// Extracts the contents of a string tensor into owned Rust [`String`]s, in row-major order.
+	///
+	/// Unlike [`Value::extract_tensor`], this does not require the `ndarray` feature.
+	pub fn extract_strings(&self) -> Result<Vec<String>> {
+		let mut tensor_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
+		ortsys![unsafe GetTensorTypeAndShape(self.ptr(), &mut tensor_info_ptr) -> Error::GetTensorTypeAndShape];
+
+		let res = (|| -> Result<usize> {
+			let mut type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
+			ortsys![unsafe GetTensorElementType(tensor_info_ptr, &mut type_sys) -> Error::GetTensorElementType];
+			let data_type: TensorElementType = type_sys.into();
+			if data_type != TensorElementType::String {
+				return Err(Error::DataTypeMismatch {
+					actual: data_type,
+					requested: TensorElementType::String
+				});
+			}
+
+			let mut len = 0;
+			ortsys![unsafe GetTensorShapeElementCount(tensor_info_ptr, &mut len) -> Error::GetTensorShapeElementCount];
+			Ok(len as _)
+		})();
+		ortsys![unsafe ReleaseTensorTypeAndShapeInfo(tensor_info_ptr)];
+		let len = res?;
+		if len == 0 {
+			return Ok(Vec::new());
+		}
+
+		// Total length of string data, not including \0 suffix
+		let mut total_length = 0;
+		ortsys![unsafe GetStringTensorDataLength(self.ptr(), &mut total_length) -> Error::GetStringTensorDataLength];
+		if total_length == 0 {
+			// every element is an empty string
+			return Ok(vec![String::new(); len]);
+		}
+
+		let mut string_contents = vec![0u8; total_length as _];
+		let mut offsets = vec![0; len];
+		ortsys![unsafe GetStringTensorContent(self.ptr(), string_contents.as_mut_ptr() as *mut std::ffi::c_void, total_length as _, offsets.as_mut_ptr(), len as _) -> Error::GetStringTensorContent];
+
+		let mut strings = Vec::with_capacity(len);
+		for (i, &offset) in offsets.iter().enumerate() {
+			let mut element_length = 0;
+			ortsys![unsafe GetStringTensorElementLength(self.ptr(), i as _, &mut element_length) -> Error::GetStringTensorElementLength];
+			let (start, element_length): (usize, usize) = (offset as _, element_length as _);
+			strings.push(String::from_utf8(string_contents[start..start + element_length].to_vec())?);
+		}
+		Ok(strings)
+	}
+
 	pub fn extract_sequence<'s>(&'s self, allocator: &Allocator) -> Result<Vec<ValueRef<'s>>> {
+		self.map_sequence(allocator, |value_ptr| ValueRef {
+			inner: unsafe { Value::from_raw_ref(value_ptr) },
+			lifetime: PhantomData
+		})
+	}
+
+	/// Extracts the elements of a sequence, e.g. the variable-length list of tensors output by a detection model, as
+	/// owned [`Value`]s allocated with `session`'s allocator.
+	///
+	/// Unlike [`Value::extract_sequence`], the elements don't borrow this value; each holds on to `session` and is
+	/// released when dropped.
+	pub fn as_sequence(&self, session: &Session) -> Result<Vec<Value>> {
+		self.map_sequence(session.allocator(), |value_ptr| unsafe { Value::from_raw(value_ptr, Arc::clone(&session.inner)) })
+	}
+
+	fn map_sequence<V>(&self, allocator: &Allocator, mut wrap: impl FnMut(*mut ort_sys::OrtValue) -> V) -> Result<Vec<V>> {
 		match self.dtype()? {
 			ValueType::Sequence(_) => {
 				let mut len: ort_sys::size_t = 0;
@@ -314,10 +470,7 @@ impl Value {
 					let mut value_ptr = ptr::null_mut();
 					ortsys![unsafe GetValue(self.ptr(), i as _, allocator.ptr, &mut value_ptr) -> Error::ExtractSequence; nonNull(value_ptr)];
 
-					vec.push(ValueRef {
-						inner: unsafe { Value::from_raw_ref(value_ptr) },
-						lifetime: PhantomData
-					});
+					vec.push(wrap(value_ptr));
 				}
 				Ok(vec)
 			}
@@ -374,7 +527,7 @@ impl Value {
 	///
 	/// `allocator` is required to be `Some` when converting a String tensor. See [`crate::Session::allocator`].
 	pub fn from_array<T: IntoTensorElementType + Debug + Clone + 'static>(input: impl OrtInput<Item = T>) -> Result<Value> {
-		let memory_info = MemoryInfo::new_cpu(AllocatorType::Arena, MemType::Default)?;
+		let memory_info = MemoryInfo::new_cpu_shared(AllocatorType::Arena, MemType::Default)?;
 
 		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
 
@@ -461,7 +614,7 @@ impl Value {
 
 	/// Construct a [`Value`] from a Rust-owned array.
 	pub fn from_string_array<T: Utf8Data + Debug + Clone + 'static>(allocator: &Allocator, input: impl OrtInput<Item = T>) -> Result<Value> {
-		let memory_info = MemoryInfo::new_cpu(AllocatorType::Arena, MemType::Default)?;
+		let memory_info = MemoryInfo::new_cpu_shared(AllocatorType::Arena, MemType::Default)?;
 
 		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
 
@@ -509,12 +662,112 @@ impl Value {
 		}
 	}
 
+	/// Returns the kind of this value (tensor, sequence, map, ...), for dispatching on outputs of unknown kinds:
+	///
+	/// ```no_run
+	/// # use ort::{OnnxType, SessionOutputs};
+	/// fn describe(outputs: &SessionOutputs) -> ort::Result<()> {
+	/// 	for (name, value) in outputs.iter() {
+	/// 		match value.value_type()? {
+	/// 			OnnxType::Tensor => println!("{name}: {:?}", value.dtype()?),
+	/// 			OnnxType::Sequence => println!("{name}: sequence"),
+	/// 			OnnxType::Map => println!("{name}: map"),
+	/// 			_ => println!("{name}: other")
+	/// 		}
+	/// 	}
+	/// 	Ok(())
+	/// }
+	/// ```
+	pub fn value_type(&self) -> Result<OnnxType> {
+		let mut ty = ort_sys::ONNXType::ONNX_TYPE_UNKNOWN;
+		ortsys![unsafe GetValueType(self.ptr(), &mut ty) -> Error::GetValueType];
+		ty.try_into()
+	}
+
 	/// Returns `true` if this value is a tensor, or false if it is another type (sequence, map)
 	pub fn is_tensor(&self) -> Result<bool> {
 		let mut result = 0;
 		ortsys![unsafe IsTensor(self.ptr(), &mut result) -> Error::GetTensorElementType];
 		Ok(result == 1)
 	}
+
+	/// Returns a pointer to the tensor data (via `GetTensorMutableData`), after checking that the tensor's element type
+	/// is `T`. For tensors allocated on a device, i.e. outputs bound with
+	/// [`IoBinding::bind_output_to_device`](crate::IoBinding::bind_output_to_device), this is a device pointer.
+	///
+	/// # Safety
+	///
+	/// The pointer is only valid while `self` (and for session outputs, the session) is alive. For values created from
+	/// Rust data, i.e. with [`Value::from_array`], the pointer aliases that data, so the usual aliasing rules apply to
+	/// any other references to it.
+	pub unsafe fn data_ptr<T: IntoTensorElementType>(&self) -> Result<*mut T> {
+		crate::run::tensor_data_ptr(self.ptr())
+	}
+
+	/// Returns the [`MemoryInfo`] describing where this tensor's data resides, i.e. to check whether an output bound with
+	/// [`IoBinding::bind_output_to_device`](crate::IoBinding::bind_output_to_device) landed in device memory. The memory
+	/// info is a copy, so it remains valid after this value is dropped.
+	pub fn memory_info(&self) -> Result<MemoryInfo> {
+		MemoryInfo::copy_from_value(self.ptr())
+	}
+
+	/// Returns `true` if this tensor's data is in memory the CPU can read directly, so it can be extracted without a
+	/// copy to the host first; `false` for an output that landed in device memory, e.g. on a CUDA GPU. See
+	/// [`MemoryInfo::is_cpu_accessible`].
+	pub fn is_on_cpu(&self) -> Result<bool> {
+		// the borrowed memory info doesn't outlive `self`, so there's no need to copy it like `memory_info` does
+		Ok(MemoryInfo::from_value(self.ptr())?.is_cpu_accessible())
+	}
+
+	/// Copies this tensor's elements into a new `Vec<T>` in host memory, after checking that the tensor's element type
+	/// is `T`.
+	///
+	/// The C API of the ONNX Runtime version `ort` binds has no function to copy a tensor between devices, so this
+	/// returns [`Error::TensorNotOnCpu`] for values in device memory. To get such an output to the host, bind it to CPU
+	/// memory instead, i.e. with [`IoBinding::bind_output_to_device`](crate::IoBinding::bind_output_to_device) and
+	/// [`MemoryInfo::new_cpu`], so ONNX Runtime copies it at the end of the run.
+	pub fn to_cpu_vec<T>(&self) -> Result<Vec<T>>
+	where
+		T: ExtractTensorData + Clone + Debug
+	{
+		let memory_info = MemoryInfo::from_value(self.ptr())?;
+		if !memory_info.is_cpu_accessible() {
+			return Err(Error::TensorNotOnCpu(memory_info.allocation_device()?.as_str()));
+		}
+		Ok(self.extract_raw_tensor::<T>()?.1.to_vec())
+	}
+
+	/// Returns `true` if this value is a sparse tensor.
+	pub fn is_sparse_tensor(&self) -> Result<bool> {
+		let mut result = 0;
+		ortsys![unsafe IsSparseTensor(self.ptr(), &mut result) -> Error::FailedTensorCheck];
+		Ok(result == 1)
+	}
+
+	/// Returns the storage format of this value if it is a sparse tensor.
+	pub fn sparse_format(&self) -> Result<SparseFormat> {
+		if !self.is_sparse_tensor()? {
+			return Err(Error::NotSparseTensor);
+		}
+		sparse::sparse_format(self.ptr())
+	}
+
+	/// Extracts the values of a sparse tensor, returning the shape of the values buffer along with the values
+	/// themselves. No data is copied.
+	pub fn extract_sparse_values<T: IntoTensorElementType>(&self) -> Result<(Vec<i64>, &[T])> {
+		if !self.is_sparse_tensor()? {
+			return Err(Error::NotSparseTensor);
+		}
+		unsafe { sparse::sparse_values(self.ptr()) }
+	}
+
+	/// Extracts one of the index buffers of a sparse tensor. No data is copied.
+	pub fn extract_sparse_indices(&self, format: SparseIndicesFormat) -> Result<&[i64]> {
+		if !self.is_sparse_tensor()? {
+			return Err(Error::NotSparseTensor);
+		}
+		unsafe { sparse::sparse_indices(self.ptr(), format) }
+	}
 }
 
 #[cfg(feature = "ndarray")]
@@ -689,6 +942,59 @@ impl<T: IntoTensorElementType + Debug + Clone + 'static> TryFrom<(Vec<i64>, Arc<
 	}
 }
 
+/// Copies the data of a tensor out into a `Vec`, in row-major order, after checking that its element type is `T`. The
+/// value is released afterwards; use [`Value::extract_raw_tensor`] to read the data without copying it.
+impl<T: IntoTensorElementType + ExtractTensorData + Clone + Debug> TryFrom<Value> for Vec<T> {
+	type Error = Error;
+	fn try_from(value: Value) -> Result<Self, Self::Error> {
+		Ok(value.extract_raw_tensor::<T>()?.1.to_vec())
+	}
+}
+
+/// Like the conversion into `Vec<T>`, but also returns the tensor's shape.
+impl<T: IntoTensorElementType + ExtractTensorData + Clone + Debug> TryFrom<Value> for (Vec<T>, Vec<i64>) {
+	type Error = Error;
+	fn try_from(value: Value) -> Result<Self, Self::Error> {
+		let (shape, data) = value.extract_raw_tensor::<T>()?;
+		Ok((data.to_vec(), shape))
+	}
+}
+
+fn argmax<T: PartialOrd>(shape: &[i64], data: &[T], axis: i64) -> Result<Vec<i64>> {
+	let rank = shape.len();
+	let axis_index = if axis < 0 { axis + rank as i64 } else { axis };
+	if axis_index < 0 || axis_index >= rank as i64 {
+		return Err(Error::DimensionOutOfRange {
+			index: axis.unsigned_abs() as usize,
+			rank
+		});
+	}
+	let axis_index = axis_index as usize;
+	let dims: Vec<usize> = shape.iter().map(|&d| d as usize).collect();
+	let outer: usize = dims[..axis_index].iter().product();
+	let len = dims[axis_index];
+	let inner: usize = dims[axis_index + 1..].iter().product();
+	if len == 0 && outer * inner > 0 {
+		return Err(Error::InvalidShape(shape.to_vec()));
+	}
+
+	let mut indices = Vec::with_capacity(outer * inner);
+	for o in 0..outer {
+		for i in 0..inner {
+			let mut best: Option<(usize, &T)> = None;
+			for k in 0..len {
+				let x = &data[(o * len + k) * inner + i];
+				// `NaN`s compare false against everything, so they are never picked over another element
+				if best.map_or(x.partial_cmp(x).is_some(), |(_, b)| x > b) {
+					best = Some((k, x));
+				}
+			}
+			indices.push(best.map_or(0, |(k, _)| k as i64));
+		}
+	}
+	Ok(indices)
+}
+
 impl Drop for Value {
 	#[tracing::instrument]
 	fn drop(&mut self) {
@@ -708,7 +1014,6 @@ pub(crate) unsafe fn extract_data_type_from_tensor_info(info_ptr: *const ort_sys
 	let mut type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
 	ortsys![GetTensorElementType(info_ptr, &mut type_sys) -> Error::GetTensorElementType];
 	assert_ne!(type_sys, ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED);
-	// This transmute should be safe since its value is read from GetTensorElementType, which we must trust
 	let mut num_dims = 0;
 	ortsys![GetDimensionsCount(info_ptr, &mut num_dims) -> Error::GetDimensionsCount];
 
@@ -721,31 +1026,54 @@ pub(crate) unsafe fn extract_data_type_from_tensor_info(info_ptr: *const ort_sys
 	})
 }
 
-pub(crate) unsafe fn extract_data_type_from_sequence_info(info_ptr: *const ort_sys::OrtSequenceTypeInfo) -> Result<ValueType> {
-	let mut element_type_info: *mut ort_sys::OrtTypeInfo = std::ptr::null_mut();
-	ortsys![GetSequenceElementType(info_ptr, &mut element_type_info) -> Error::GetSequenceElementType];
-
+/// Extracts the [`ValueType`] described by an [`ort_sys::OrtTypeInfo`]. The type info is not released.
+pub(crate) unsafe fn extract_data_type_from_type_info(typeinfo_ptr: *const ort_sys::OrtTypeInfo) -> Result<ValueType> {
 	let mut ty: ort_sys::ONNXType = ort_sys::ONNXType::ONNX_TYPE_UNKNOWN;
-	let status = ortsys![unsafe GetOnnxTypeFromTypeInfo(element_type_info, &mut ty)];
+	let status = ortsys![unsafe GetOnnxTypeFromTypeInfo(typeinfo_ptr, &mut ty)];
 	status_to_result(status).map_err(Error::GetOnnxTypeFromTypeInfo)?;
-
 	match ty {
-		ort_sys::ONNXType::ONNX_TYPE_TENSOR => {
+		ort_sys::ONNXType::ONNX_TYPE_TENSOR | ort_sys::ONNXType::ONNX_TYPE_SPARSETENSOR => {
 			let mut info_ptr: *const ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
-			ortsys![unsafe CastTypeInfoToTensorInfo(element_type_info, &mut info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(info_ptr)];
-			let ty = unsafe { extract_data_type_from_tensor_info(info_ptr)? };
-			Ok(ValueType::Sequence(Box::new(ty)))
+			ortsys![unsafe CastTypeInfoToTensorInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(info_ptr)];
+			extract_data_type_from_tensor_info(info_ptr)
+		}
+		ort_sys::ONNXType::ONNX_TYPE_SEQUENCE => {
+			let mut info_ptr: *const ort_sys::OrtSequenceTypeInfo = std::ptr::null_mut();
+			ortsys![unsafe CastTypeInfoToSequenceTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToSequenceTypeInfo; nonNull(info_ptr)];
+			extract_data_type_from_sequence_info(info_ptr)
 		}
 		ort_sys::ONNXType::ONNX_TYPE_MAP => {
 			let mut info_ptr: *const ort_sys::OrtMapTypeInfo = std::ptr::null_mut();
-			ortsys![unsafe CastTypeInfoToMapTypeInfo(element_type_info, &mut info_ptr) -> Error::CastTypeInfoToMapTypeInfo; nonNull(info_ptr)];
-			let ty = unsafe { extract_data_type_from_map_info(info_ptr)? };
-			Ok(ValueType::Sequence(Box::new(ty)))
+			ortsys![unsafe CastTypeInfoToMapTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToMapTypeInfo; nonNull(info_ptr)];
+			extract_data_type_from_map_info(info_ptr)
+		}
+		ort_sys::ONNXType::ONNX_TYPE_OPTIONAL => {
+			let mut info_ptr: *const ort_sys::OrtOptionalTypeInfo = std::ptr::null_mut();
+			ortsys![unsafe CastTypeInfoToOptionalTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToOptionalTypeInfo; nonNull(info_ptr)];
+			extract_data_type_from_optional_info(info_ptr)
 		}
 		_ => unreachable!()
 	}
 }
 
+pub(crate) unsafe fn extract_data_type_from_sequence_info(info_ptr: *const ort_sys::OrtSequenceTypeInfo) -> Result<ValueType> {
+	let mut element_type_info: *mut ort_sys::OrtTypeInfo = std::ptr::null_mut();
+	ortsys![GetSequenceElementType(info_ptr, &mut element_type_info) -> Error::GetSequenceElementType; nonNull(element_type_info)];
+
+	let ty = extract_data_type_from_type_info(element_type_info);
+	ortsys![unsafe ReleaseTypeInfo(element_type_info)];
+	Ok(ValueType::Sequence(Box::new(ty?)))
+}
+
+pub(crate) unsafe fn extract_data_type_from_optional_info(info_ptr: *const ort_sys::OrtOptionalTypeInfo) -> Result<ValueType> {
+	let mut contained_type_info: *mut ort_sys::OrtTypeInfo = std::ptr::null_mut();
+	ortsys![GetOptionalContainedTypeInfo(info_ptr, &mut contained_type_info) -> Error::GetOptionalContainedTypeInfo; nonNull(contained_type_info)];
+
+	let ty = extract_data_type_from_type_info(contained_type_info);
+	ortsys![unsafe ReleaseTypeInfo(contained_type_info)];
+	Ok(ValueType::Optional(Box::new(ty?)))
+}
+
 pub(crate) unsafe fn extract_data_type_from_map_info(info_ptr: *const ort_sys::OrtMapTypeInfo) -> Result<ValueType> {
 	let mut key_type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
 	ortsys![GetMapKeyType(info_ptr, &mut key_type_sys) -> Error::GetMapKeyType];
@@ -757,6 +1085,7 @@ pub(crate) unsafe fn extract_data_type_from_map_info(info_ptr: *const ort_sys::O
 	ortsys![unsafe CastTypeInfoToTensorInfo(value_type_info, &mut value_info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(value_info_ptr)];
 	let mut value_type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
 	ortsys![GetTensorElementType(value_info_ptr, &mut value_type_sys) -> Error::GetTensorElementType];
+	ortsys![unsafe ReleaseTypeInfo(value_type_info)];
 	assert_ne!(value_type_sys, ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED);
 
 	Ok(ValueType::Map {
@@ -777,6 +1106,7 @@ mod tests {
 		let v: Vec<f32> = vec![1., 2., 3., 4., 5.];
 		let value = Value::from_array(Array1::from_vec(v.clone()))?;
 		assert!(value.is_tensor()?);
+		assert!(value.is_on_cpu()?);
 		assert_eq!(value.tensor_element_type()?, TensorElementType::Float32);
 		assert_eq!(
 			value.dtype()?,
@@ -793,6 +1123,18 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_value_type_compatibility() {
+		let tensor = |ty, dimensions: &[i64]| ValueType::Tensor { ty, dimensions: dimensions.to_vec() };
+		let image = tensor(TensorElementType::Float32, &[1, 3, 224, 224]);
+		assert!(image.is_compatible_with(&tensor(TensorElementType::Float32, &[-1, 3, 224, 224])));
+		assert!(image.is_compatible_with(&tensor(TensorElementType::Float32, &[3, 1, 224])));
+		assert!(!image.is_compatible_with(&tensor(TensorElementType::Float32, &[1, 3, 256, 256])));
+		assert!(!image.is_compatible_with(&tensor(TensorElementType::Float16, &[1, 3, 224, 224])));
+		assert!(ValueType::Sequence(Box::new(image.clone())).is_compatible_with(&ValueType::Sequence(Box::new(image.clone()))));
+		assert!(!ValueType::Sequence(Box::new(image.clone())).is_compatible_with(&image));
+	}
+
 	#[test]
 	#[cfg(feature = "ndarray")]
 	fn test_tensor_lifetimes() -> crate::Result<()> {
@@ -829,4 +1171,60 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_value_into_vec() -> crate::Result<()> {
+		let v: Vec<f32> = vec![1., 2., 3., 4., 5., 6.];
+		let value = Value::from_array((vec![2, 3], Arc::new(v.clone().into_boxed_slice())))?;
+		assert_eq!(Vec::<f32>::try_from(value)?, v);
+
+		let value = Value::from_array((vec![2, 3], Arc::new(v.clone().into_boxed_slice())))?;
+		let (data, shape): (Vec<f32>, Vec<i64>) = value.try_into()?;
+		assert_eq!((data, shape), (v.clone(), vec![2, 3]));
+
+		let value = Value::from_array((vec![2, 3], Arc::new(v.into_boxed_slice())))?;
+		assert!(matches!(
+			Vec::<i64>::try_from(value),
+			Err(Error::DataTypeMismatch { actual: TensorElementType::Float32, requested: TensorElementType::Int64 })
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_argmax() {
+		let data = [0.1f32, 0.7, 0.2, 0.5, 0.4, 0.1];
+		assert_eq!(super::argmax(&[2, 3], &data, -1).unwrap(), [1, 0]);
+		assert_eq!(super::argmax(&[2, 3], &data, 1).unwrap(), [1, 0]);
+		assert_eq!(super::argmax(&[2, 3], &data, 0).unwrap(), [1, 0, 0]);
+		assert_eq!(super::argmax(&[2, 1, 3], &data, 2).unwrap(), [1, 0]);
+		assert!(matches!(super::argmax(&[2, 3], &data, 2), Err(Error::DimensionOutOfRange { index: 2, rank: 2 })));
+		assert!(matches!(super::argmax(&[2, 3], &data, -3), Err(Error::DimensionOutOfRange { index: 3, rank: 2 })));
+		assert_eq!(super::argmax(&[1, 3], &[f64::NAN, -1.0, -2.0], -1).unwrap(), [1]);
+		assert_eq!(super::argmax(&[0, 3], &[] as &[f32], -1).unwrap(), [] as [i64; 0]);
+	}
+
+	#[test]
+	fn test_dequantize() -> crate::Result<()> {
+		let value = Value::from_array((vec![3], Arc::new(vec![0u8, 128, 255].into_boxed_slice())))?;
+		assert_eq!(value.dequantize_to_f32(0.5, 128)?, [-64.0, 0.0, 63.5]);
+		let value = Value::from_array((vec![3], Arc::new(vec![-128i8, 0, 127].into_boxed_slice())))?;
+		assert_eq!(value.dequantize_to_f32(0.25, -128)?, [0.0, 32.0, 63.75]);
+		let value = Value::from_array((vec![1], Arc::new(vec![1.0f32].into_boxed_slice())))?;
+		assert!(matches!(value.dequantize_to_f32(1.0, 0), Err(Error::NotQuantized(TensorElementType::Float32))));
+
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "serde")]
+	fn test_value_type_serde() {
+		let ty = ValueType::Sequence(Box::new(ValueType::Tensor {
+			ty: TensorElementType::Float32,
+			dimensions: vec![-1, 3]
+		}));
+		let json = serde_json::to_string(&ty).unwrap();
+		assert_eq!(json, r#"{"Sequence":{"Tensor":{"ty":"float32","dimensions":[-1,3]}}}"#);
+		assert_eq!(serde_json::from_str::<ValueType>(&json).unwrap(), ty);
+	}
 }
@@ -0,0 +1,29 @@
+mod fixtures;
+
+use ort::{AllocatedBuffer, Names, RustOwnerValue, TensorElementType};
+use test_log::test;
+
+use self::fixtures::{test_session, Dim};
+
+#[test]
+fn ort_allocated_input() -> ort::Result<()> {
+	let session = test_session(&fixtures::identity(TensorElementType::Int64, &[Dim::Fixed(2), Dim::Fixed(2)]))?;
+
+	let mut input = RustOwnerValue::<AllocatedBuffer<i64>>::new_allocated(&[2, 2], session.allocator()).expect("Could not allocate input");
+	assert_eq!(input.as_slice(), [0; 4]);
+	input.as_mut_slice().copy_from_slice(&[1, 2, 3, 4]);
+
+	let mut outputs = [RustOwnerValue::<Vec<i64>>::zeros(&[2, 2]).expect("Could not create output")];
+	session.run_with_io_ref(&Names::single("x"), std::slice::from_ref(&input), &Names::single("y"), &mut outputs, None)?;
+	assert_eq!(outputs[0].as_slice(), [1, 2, 3, 4]);
+
+	// the buffer owns the ONNX Runtime allocation, so it outlives the value
+	let buffer = input.into_container();
+	assert_eq!(&*buffer, [1, 2, 3, 4]);
+
+	let empty = RustOwnerValue::<AllocatedBuffer<f32>>::new_allocated(&[0, 3], session.allocator()).expect("Could not allocate empty input");
+	assert!(empty.as_slice().is_empty());
+	assert!(empty.to_cpu_vec::<f32>()?.is_empty());
+	assert!(matches!(empty.to_cpu_vec::<i64>(), Err(ort::Error::DataTypeMismatch { .. })));
+	Ok(())
+}
@@ -0,0 +1,38 @@
+use std::path::Path;
+
+use ort::{Names, RustOwnerValue, Session};
+use test_log::test;
+
+/// Models without inputs should run with empty input names & values. constant.onnx outputs the constant `[1, 2, 3]`;
+/// it is equivalent to the model produced by:
+///
+/// ```python
+/// import onnx
+/// from onnx import helper, TensorProto
+///
+/// value = helper.make_tensor('value', TensorProto.FLOAT, [3], [1.0, 2.0, 3.0])
+/// node = helper.make_node('Constant', [], ['y'], name='constant', value=value)
+/// graph = helper.make_graph([node], 'constant', [], [helper.make_tensor_value_info('y', TensorProto.FLOAT, [3])])
+/// onnx.save(helper.make_model(graph, opset_imports=[helper.make_opsetid('', 13)], ir_version=8), 'constant.onnx')
+/// ```
+#[test]
+fn constant_without_inputs() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("constant.onnx"))
+		.expect("Could not load model");
+	assert!(session.inputs.is_empty());
+	assert_eq!(session.input_count()?, 0);
+	assert_eq!(session.output_metadata().iter().map(|output| output.name.as_str()).collect::<Vec<_>>(), ["y"]);
+
+	let input_names = Names::from(Vec::<&str>::new());
+	let inputs: [RustOwnerValue<Vec<f32>>; 0] = [];
+	let output_names = Names::single("y");
+	let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[3]).expect("Could not create output")];
+
+	session.run_with_io_ref(&input_names, &inputs, &output_names, &mut outputs, None)?;
+	assert_eq!(outputs[0].as_slice(), &[1.0, 2.0, 3.0]);
+
+	Ok(())
+}
@@ -0,0 +1,31 @@
+use std::path::Path;
+
+use ort::{DevicePolicy, ExecutionProvider, Names, RustOwnerValue, Session};
+use test_log::test;
+
+#[test]
+fn device_policy_order() {
+	let policy = DevicePolicy::new().tensorrt(1).cuda(1).cuda(0).cpu();
+	assert_eq!(
+		policy.execution_providers().iter().map(|ep| ep.as_str()).collect::<Vec<_>>(),
+		["TensorrtExecutionProvider", "CUDAExecutionProvider", "CUDAExecutionProvider", "CPUExecutionProvider"]
+	);
+}
+
+/// Without a usable GPU, the session falls back to the CPU at the end of the policy.
+#[test]
+fn device_policy_fallback() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let policy = DevicePolicy::new().cuda(0).cpu();
+	let (session, execution_provider) = Session::builder()?
+		.with_model_from_file_with_policy(&policy, Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("constant.onnx"))?;
+	assert!(["CUDAExecutionProvider", "CPUExecutionProvider"].contains(&execution_provider.as_str()));
+
+	let inputs: [RustOwnerValue<Vec<f32>>; 0] = [];
+	let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[3]).expect("Could not create output")];
+	session.run_with_io_ref(&Names::from(Vec::<&str>::new()), &inputs, &Names::single("y"), &mut outputs, None)?;
+	assert_eq!(outputs[0].as_slice(), [1.0, 2.0, 3.0]);
+
+	Ok(())
+}
@@ -0,0 +1,32 @@
+mod fixtures;
+
+use ort::{inputs, Names, RustOwnerValue, TensorElementType};
+use test_log::test;
+
+use self::fixtures::{test_session, Dim};
+
+/// Zero-element tensors, i.e. an empty batch, can be created from empty buffers and run through a model.
+#[test]
+fn empty_batch() -> ort::Result<()> {
+	let session = test_session(&fixtures::identity(TensorElementType::Float32, &[Dim::Symbolic("batch"), Dim::Fixed(3)]))?;
+
+	let inputs = [RustOwnerValue::new(&[0, 3], Vec::<f32>::new()).expect("Could not create empty input")];
+	assert_eq!(inputs[0].type_and_shape()?.element_count, 0);
+
+	// into pre-allocated (empty) outputs
+	let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[0, 3]).expect("Could not create empty output")];
+	session.run_with_io_ref(&Names::single("x"), &inputs, &Names::single("y"), &mut outputs, None)?;
+	assert!(outputs[0].as_slice().is_empty());
+	assert!(outputs[0].to_cpu_vec::<f32>()?.is_empty());
+
+	// into outputs allocated by ONNX Runtime
+	let array = ndarray::CowArray::from(ndarray::Array2::<f32>::zeros((0, 3)).into_dyn());
+	let outputs = session.run(inputs![&array]?)?;
+	let (shape, data) = outputs["y"].extract_raw_tensor::<f32>()?;
+	assert_eq!(shape, [0, 3]);
+	assert!(data.is_empty());
+	assert!(outputs["y"].to_cpu_vec::<f32>()?.is_empty());
+	assert_eq!(outputs["y"].extract_tensor::<f32>()?.view().shape(), [0, 3]);
+
+	Ok(())
+}
@@ -0,0 +1,107 @@
+mod fixtures;
+
+use ndarray::{arr1, Array1, CowArray};
+use ort::{inputs, Names, RustOwnerValue, TensorElementType, Value, ValueType};
+use test_log::test;
+
+use self::fixtures::{test_session, Dim};
+
+#[test]
+fn identity_fixture() -> ort::Result<()> {
+	let session = test_session(&fixtures::identity(TensorElementType::Int64, &[Dim::Fixed(2), Dim::Symbolic("n")]))?;
+	assert_eq!(
+		session.inputs[0].input_type,
+		ValueType::Tensor {
+			ty: TensorElementType::Int64,
+			dimensions: vec![2, -1]
+		}
+	);
+
+	let array = CowArray::from(ndarray::Array2::<i64>::from_shape_vec((2, 2), vec![1, 2, 3, 4]).unwrap().into_dyn());
+	let outputs = session.run(inputs![&array]?)?;
+	let (shape, data) = outputs["y"].extract_raw_tensor::<i64>()?;
+	assert_eq!(shape, [2, 2]);
+	assert_eq!(data, [1, 2, 3, 4]);
+	Ok(())
+}
+
+#[test]
+fn add_fixture() -> ort::Result<()> {
+	let session = test_session(&fixtures::add())?;
+	assert_eq!(session.input_names()?, ["a", "b"]);
+	assert_eq!(session.output_names()?, ["c"]);
+	let a = CowArray::from(arr1(&[1.0f32, 2.0, 3.0]).into_dyn());
+	let b = CowArray::from(Array1::<f32>::ones(3).into_dyn());
+	let outputs = session.run(inputs!["a" => &a, "b" => &b]?)?;
+	assert_eq!(outputs["c"].extract_raw_tensor::<f32>()?.1, [2.0, 3.0, 4.0]);
+	Ok(())
+}
+
+#[test]
+fn model_header_from_bytes() -> ort::Result<()> {
+	let header = ort::ModelHeader::from_bytes(&fixtures::add())?;
+	assert_eq!(header.ir_version, 8);
+	assert_eq!(header.opset_imports, [(String::new(), 13)]);
+	Ok(())
+}
+
+#[test]
+fn to_cpu_vec_checks_type() -> ort::Result<()> {
+	let session = test_session(&fixtures::add())?;
+	let a = CowArray::from(arr1(&[1.0f32, 2.0, 3.0]).into_dyn());
+	let b = CowArray::from(Array1::<f32>::ones(3).into_dyn());
+	let outputs = session.run(inputs!["a" => &a, "b" => &b]?)?;
+	assert_eq!(outputs["c"].to_cpu_vec::<f32>()?, [2.0, 3.0, 4.0]);
+	assert!(matches!(outputs["c"].to_cpu_vec::<i64>(), Err(ort::Error::DataTypeMismatch { .. })));
+
+	let owned = RustOwnerValue::new(&[3], vec![1.0f32, 2.0, 3.0]).expect("Could not create value");
+	assert_eq!(owned.to_cpu_vec::<f32>()?, [1.0, 2.0, 3.0]);
+	assert!(matches!(owned.to_cpu_vec::<i32>(), Err(ort::Error::DataTypeMismatch { .. })));
+	Ok(())
+}
+
+#[test]
+fn constant_fixture() -> ort::Result<()> {
+	let session = test_session(&fixtures::constant(&[1.0, 2.0, 3.0]))?;
+	assert!(session.inputs.is_empty());
+	let inputs: [RustOwnerValue<Vec<f32>>; 0] = [];
+	let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[3]).expect("Could not create output")];
+	session.run_with_io_ref(&Names::from(Vec::<&str>::new()), &inputs, &Names::single("y"), &mut outputs, None)?;
+	assert_eq!(outputs[0].as_slice(), [1.0, 2.0, 3.0]);
+	Ok(())
+}
+
+#[test]
+fn memory_info_outlives_value() -> ort::Result<()> {
+	let session = test_session(&fixtures::add())?;
+	let a = CowArray::from(arr1(&[1.0f32]).into_dyn());
+	let memory_info = {
+		let outputs = session.run(inputs!["a" => &a, "b" => &a]?)?;
+		outputs["c"].memory_info()?
+	};
+	assert_eq!(memory_info.allocation_device()?, ort::AllocationDevice::CPU);
+
+	let input = RustOwnerValue::new(&[1], vec![1.0f32]).expect("Could not create input");
+	let memory_info = input.memory_info()?;
+	drop(input);
+	assert!(memory_info.is_cpu_accessible());
+	Ok(())
+}
+
+#[test]
+fn string_identity_fixture() -> ort::Result<()> {
+	let session = test_session(&fixtures::string_identity())?;
+	let array = CowArray::from(Array1::from(vec!["hello".to_owned(), "world".to_owned()]).into_dyn());
+	let outputs = session.run(inputs![Value::from_string_array(session.allocator(), &array)?]?)?;
+	assert_eq!(outputs["y"].extract_strings()?, ["hello", "world"]);
+	Ok(())
+}
+
+#[test]
+fn tensors_close() {
+	fixtures::assert_tensors_close(&[1.0, 2.0, 100.0], &[1.0, 2.001, 100.1], 1e-3, 1e-3);
+	let mismatch = std::panic::catch_unwind(|| fixtures::assert_tensors_close(&[1.0, 2.0, 3.0], &[1.0, 2.5, 3.5], 0.0, 1e-3));
+	let message = *mismatch.expect_err("tensors should differ").downcast::<String>().unwrap();
+	assert!(message.starts_with("tensors differ at index 1: 2 != 2.5"), "{message}");
+	assert!(message.ends_with("2 of 3 elements differ"), "{message}");
+}
@@ -0,0 +1,284 @@
+//! Minimal ONNX models generated at runtime, shared by the integration tests.
+//!
+//! Include it from a test file with `mod fixtures;`. Each model is encoded by hand with the tiny protobuf writer below,
+//! so no model files or Python tooling are needed; the doc comment on each fixture shows the equivalent `onnx.helper`
+//! code. All models use IR version 8 and opset 13. It also holds helpers to compare outputs against reference values
+//! with a tolerance.
+
+#![allow(dead_code)]
+
+use ort::{ONNXTensorElementDataType, RustOwnerValue, Session, TensorElementType};
+
+const IR_VERSION: u64 = 8;
+const OPSET_VERSION: u64 = 13;
+
+/// A dimension of a graph input or output.
+#[derive(Debug, Clone, Copy)]
+pub enum Dim {
+	Fixed(i64),
+	Symbolic(&'static str)
+}
+
+/// Initializes the environment and creates a session from the given model bytes.
+pub fn test_session(model: &[u8]) -> ort::Result<Session> {
+	ort::init().with_name("integration_test").commit()?;
+	Session::builder()?.with_intra_threads(1)?.with_model_from_memory(model)
+}
+
+/// Asserts that `actual` and `expected` have the same length and that every pair of elements satisfies
+/// `|actual - expected| <= atol + rtol * |expected|`, like `numpy.allclose`. NaNs are never considered close.
+///
+/// Panics with the first mismatching index, both values and the number of mismatching elements otherwise.
+#[track_caller]
+pub fn assert_tensors_close(actual: &[f32], expected: &[f32], rtol: f32, atol: f32) {
+	assert_eq!(actual.len(), expected.len(), "tensors have different lengths");
+	let is_close = |(&a, &e): (&f32, &f32)| (a - e).abs() <= atol + rtol * e.abs();
+	let mut mismatches = actual.iter().zip(expected).enumerate().filter(|&(_, pair)| !is_close(pair));
+	if let Some((index, (&a, &e))) = mismatches.next() {
+		panic!(
+			"tensors differ at index {index}: {a} != {e} (difference {}, tolerance {}); {} of {} elements differ",
+			(a - e).abs(),
+			atol + rtol * e.abs(),
+			mismatches.count() + 1,
+			actual.len()
+		);
+	}
+}
+
+/// Like [`assert_tensors_close`], but for two `f32` tensors, which must also have the same shape.
+#[track_caller]
+pub fn assert_values_close<A, E>(actual: &RustOwnerValue<A>, expected: &RustOwnerValue<E>, rtol: f32, atol: f32) {
+	let actual_shape = actual.type_and_shape().expect("Could not get shape").dimensions;
+	let expected_shape = expected.type_and_shape().expect("Could not get shape").dimensions;
+	assert_eq!(actual_shape, expected_shape, "tensors have different shapes");
+	let actual = actual.to_cpu_vec::<f32>().expect("Could not read actual tensor");
+	let expected = expected.to_cpu_vec::<f32>().expect("Could not read expected tensor");
+	assert_tensors_close(&actual, &expected, rtol, atol);
+}
+
+/// `y = Identity(x)`, where `x` and `y` are tensors of `ty` with the given dimensions.
+///
+/// ```python
+/// x = helper.make_tensor_value_info('x', ty, dims)
+/// y = helper.make_tensor_value_info('y', ty, dims)
+/// graph = helper.make_graph([helper.make_node('Identity', ['x'], ['y'])], 'identity', [x], [y])
+/// ```
+pub fn identity(ty: TensorElementType, dims: &[Dim]) -> Vec<u8> {
+	let graph = Message::new()
+		.message(1, node("Identity", &["x"], &["y"], None))
+		.string(2, "identity")
+		.message(11, value_info("x", ty, dims))
+		.message(12, value_info("y", ty, dims));
+	model(graph)
+}
+
+/// `y = Identity(x)` over a 1-D string tensor of dynamic length `n`.
+pub fn string_identity() -> Vec<u8> {
+	identity(TensorElementType::String, &[Dim::Symbolic("n")])
+}
+
+/// `c = Add(a, b)` over 1-D float tensors of dynamic length `n`.
+///
+/// ```python
+/// a = helper.make_tensor_value_info('a', TensorProto.FLOAT, ['n'])
+/// b = helper.make_tensor_value_info('b', TensorProto.FLOAT, ['n'])
+/// c = helper.make_tensor_value_info('c', TensorProto.FLOAT, ['n'])
+/// graph = helper.make_graph([helper.make_node('Add', ['a', 'b'], ['c'])], 'add', [a, b], [c])
+/// ```
+pub fn add() -> Vec<u8> {
+	let dims = [Dim::Symbolic("n")];
+	let graph = Message::new()
+		.message(1, node("Add", &["a", "b"], &["c"], None))
+		.string(2, "add")
+		.message(11, value_info("a", TensorElementType::Float32, &dims))
+		.message(11, value_info("b", TensorElementType::Float32, &dims))
+		.message(12, value_info("c", TensorElementType::Float32, &dims));
+	model(graph)
+}
+
+/// `y = Identity(x)` and `z = Neg(x)` over 1-D float tensors of dynamic length `n`, for selecting a subset of outputs.
+///
+/// ```python
+/// x = helper.make_tensor_value_info('x', TensorProto.FLOAT, ['n'])
+/// y = helper.make_tensor_value_info('y', TensorProto.FLOAT, ['n'])
+/// z = helper.make_tensor_value_info('z', TensorProto.FLOAT, ['n'])
+/// nodes = [helper.make_node('Identity', ['x'], ['y']), helper.make_node('Neg', ['x'], ['z'])]
+/// graph = helper.make_graph(nodes, 'identity_and_neg', [x], [y, z])
+/// ```
+pub fn identity_and_neg() -> Vec<u8> {
+	let dims = [Dim::Symbolic("n")];
+	let graph = Message::new()
+		.message(1, node("Identity", &["x"], &["y"], None))
+		.message(1, node("Neg", &["x"], &["z"], None))
+		.string(2, "identity_and_neg")
+		.message(11, value_info("x", TensorElementType::Float32, &dims))
+		.message(12, value_info("y", TensorElementType::Float32, &dims))
+		.message(12, value_info("z", TensorElementType::Float32, &dims));
+	model(graph)
+}
+
+/// `y = LabelEncoder(x)`, mapping each string of the 1-D tensor `x` to the integer at the same position in `values` as
+/// the string in `keys`, or `-1` for unknown strings.
+///
+/// ```python
+/// x = helper.make_tensor_value_info('x', TensorProto.STRING, ['n'])
+/// y = helper.make_tensor_value_info('y', TensorProto.INT64, ['n'])
+/// node = helper.make_node('LabelEncoder', ['x'], ['y'], domain='ai.onnx.ml', keys_strings=keys, values_int64s=values)
+/// graph = helper.make_graph([node], 'label_encoder', [x], [y])
+/// ```
+pub fn label_encoder(keys: &[&str], values: &[i64]) -> Vec<u8> {
+	let dims = [Dim::Symbolic("n")];
+	let mut keys_strings = Message::new().string(1, "keys_strings").varint(20, 8);
+	for key in keys {
+		keys_strings = keys_strings.string(9, key);
+	}
+	let mut values_int64s = Message::new().string(1, "values_int64s").varint(20, 7);
+	for value in values {
+		values_int64s = values_int64s.varint(8, *value as u64);
+	}
+	let node = node("LabelEncoder", &["x"], &["y"], Some(keys_strings))
+		.message(5, values_int64s)
+		.string(7, "ai.onnx.ml");
+	let graph = Message::new()
+		.message(1, node)
+		.string(2, "label_encoder")
+		.message(11, value_info("x", TensorElementType::String, &dims))
+		.message(12, value_info("y", TensorElementType::Int64, &dims));
+	model_with_opsets(graph, &[("ai.onnx.ml", 2)])
+}
+
+/// A model without inputs whose single output `y` is the float constant `values`.
+///
+/// ```python
+/// value = helper.make_tensor('value', TensorProto.FLOAT, [len(values)], values)
+/// node = helper.make_node('Constant', [], ['y'], value=value)
+/// graph = helper.make_graph([node], 'constant', [], [helper.make_tensor_value_info('y', TensorProto.FLOAT, [len(values)])])
+/// ```
+pub fn constant(values: &[f32]) -> Vec<u8> {
+	let len = values.len() as i64;
+	let tensor = Message::new()
+		.varint(1, len as u64)
+		.varint(2, element_type(TensorElementType::Float32))
+		.bytes(4, &values.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<_>>())
+		.string(8, "value");
+	let attribute = Message::new().string(1, "value").message(5, tensor).varint(20, 4);
+	let graph = Message::new()
+		.message(1, node("Constant", &[], &["y"], Some(attribute)))
+		.string(2, "constant")
+		.message(12, value_info("y", TensorElementType::Float32, &[Dim::Fixed(len)]));
+	model(graph)
+}
+
+/// Like [`identity`] over a float tensor of dynamic length `n`, but with an initializer no node uses, which ONNX Runtime
+/// warns about while loading the model:
+/// ```python
+/// w = helper.make_tensor('w', TensorProto.FLOAT, [1], [0.0])
+/// graph = helper.make_graph([helper.make_node('Identity', ['x'], ['y'])], 'identity', [x], [y], initializer=[w])
+/// ```
+pub fn identity_with_unused_initializer() -> Vec<u8> {
+	let dims = [Dim::Symbolic("n")];
+	let w = Message::new()
+		.varint(1, 1)
+		.varint(2, element_type(TensorElementType::Float32))
+		.bytes(4, &0.0f32.to_le_bytes())
+		.string(8, "w");
+	let graph = Message::new()
+		.message(1, node("Identity", &["x"], &["y"], None))
+		.string(2, "identity")
+		.message(5, w)
+		.message(11, value_info("x", TensorElementType::Float32, &dims))
+		.message(12, value_info("y", TensorElementType::Float32, &dims));
+	model(graph)
+}
+
+fn element_type(ty: TensorElementType) -> u64 {
+	ONNXTensorElementDataType::from(ty) as i32 as u64
+}
+
+fn model(graph: Message) -> Vec<u8> {
+	model_with_opsets(graph, &[])
+}
+
+/// Like [`model`], but also imports the given `(domain, version)` operator sets.
+fn model_with_opsets(graph: Message, opsets: &[(&str, u64)]) -> Vec<u8> {
+	let mut model = Message::new()
+		.varint(1, IR_VERSION)
+		.message(7, graph)
+		.message(8, Message::new().string(1, "").varint(2, OPSET_VERSION));
+	for (domain, version) in opsets {
+		model = model.message(8, Message::new().string(1, domain).varint(2, *version));
+	}
+	model.into_bytes()
+}
+
+fn node(op_type: &str, inputs: &[&str], outputs: &[&str], attribute: Option<Message>) -> Message {
+	let mut node = Message::new();
+	for input in inputs {
+		node = node.string(1, input);
+	}
+	for output in outputs {
+		node = node.string(2, output);
+	}
+	node = node.string(4, op_type);
+	if let Some(attribute) = attribute {
+		node = node.message(5, attribute);
+	}
+	node
+}
+
+fn value_info(name: &str, ty: TensorElementType, dims: &[Dim]) -> Message {
+	let mut shape = Message::new();
+	for dim in dims {
+		shape = shape.message(
+			1,
+			match dim {
+				Dim::Fixed(value) => Message::new().varint(1, *value as u64),
+				Dim::Symbolic(param) => Message::new().string(2, param)
+			}
+		);
+	}
+	let tensor_type = Message::new().varint(1, element_type(ty)).message(2, shape);
+	Message::new().string(1, name).message(2, Message::new().message(1, tensor_type))
+}
+
+/// Just enough of a protobuf writer to encode the messages above.
+struct Message(Vec<u8>);
+
+impl Message {
+	fn new() -> Self {
+		Self(Vec::new())
+	}
+
+	fn write_varint(&mut self, mut value: u64) {
+		while value >= 0x80 {
+			self.0.push((value as u8) | 0x80);
+			value >>= 7;
+		}
+		self.0.push(value as u8);
+	}
+
+	fn varint(mut self, field: u64, value: u64) -> Self {
+		self.write_varint(field << 3);
+		self.write_varint(value);
+		self
+	}
+
+	fn bytes(mut self, field: u64, value: &[u8]) -> Self {
+		self.write_varint((field << 3) | 2);
+		self.write_varint(value.len() as u64);
+		self.0.extend_from_slice(value);
+		self
+	}
+
+	fn string(self, field: u64, value: &str) -> Self {
+		self.bytes(field, value.as_bytes())
+	}
+
+	fn message(self, field: u64, value: Message) -> Self {
+		self.bytes(field, &value.0)
+	}
+
+	fn into_bytes(self) -> Vec<u8> {
+		self.0
+	}
+}
@@ -0,0 +1,68 @@
+use std::path::Path;
+
+use ort::{Names, RustOwnerValue, Session};
+use test_log::test;
+
+/// add_initializer.onnx computes `y = x + w`, where `w` is an initializer holding `[1, 1, 1]`; it is equivalent to the
+/// model produced by:
+///
+/// ```python
+/// import onnx
+/// from onnx import helper, TensorProto
+///
+/// w = helper.make_tensor('w', TensorProto.FLOAT, [3], [1.0, 1.0, 1.0])
+/// node = helper.make_node('Add', ['x', 'w'], ['y'], name='add')
+/// x = helper.make_tensor_value_info('x', TensorProto.FLOAT, [3])
+/// y = helper.make_tensor_value_info('y', TensorProto.FLOAT, [3])
+/// graph = helper.make_graph([node], 'add', [x], [y], initializer=[w])
+/// onnx.save(helper.make_model(graph, opset_imports=[helper.make_opsetid('', 13)], ir_version=8), 'add_initializer.onnx')
+/// ```
+#[test]
+fn override_initializer() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let model_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("add_initializer.onnx");
+	let run = |session: &Session| -> ort::Result<Vec<f32>> {
+		let inputs = [RustOwnerValue::new(&[3], vec![0.5f32; 3]).expect("Could not create input")];
+		let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[3]).expect("Could not create output")];
+		session.run_with_io_ref(&Names::single("x"), &inputs, &Names::single("y"), &mut outputs, None)?;
+		Ok(outputs[0].to_vec())
+	};
+
+	let session = Session::builder()?.with_model_from_file(&model_path).expect("Could not load model");
+	assert_eq!(run(&session)?, [1.5, 1.5, 1.5]);
+
+	let builder = Session::builder()?.override_initializer("w", &[3], vec![10.0f32, 20.0, 30.0])?;
+	let session = builder.with_model_from_file(&model_path).expect("Could not load model");
+	assert_eq!(run(&session)?, [10.5, 20.5, 30.5]);
+
+	assert!(matches!(
+		Session::builder()?.override_initializer("w", &[3], vec![1.0f32; 2]),
+		Err(ort::Error::CreateInitializer { .. })
+	));
+
+	Ok(())
+}
+
+#[test]
+fn omit_optional_inputs() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let model_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("add_initializer.onnx");
+	let session = Session::builder()?.with_model_from_file(&model_path).expect("Could not load model");
+	assert_eq!(session.overridable_initializer_names(), ["w"]);
+	let x = || Some(RustOwnerValue::new(&[3], vec![0.5f32; 3]).expect("Could not create input"));
+	let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[3]).expect("Could not create output")];
+	let output_names = Names::single("y");
+
+	// the initializer `w` may be omitted, in which case its default value is used
+	session.run_with_optional_inputs(&Names::from(vec!["x", "w"]), &[x(), None], &output_names, &mut outputs, None)?;
+	assert_eq!(outputs[0].as_slice(), [1.5, 1.5, 1.5]);
+
+	let result = session.run_with_optional_inputs(&Names::from(vec!["x", "v"]), &[x(), None], &output_names, &mut outputs, None);
+	assert!(matches!(result, Err(ort::Error::UnknownInput(name)) if name == "v"));
+	let result = session.run_with_optional_inputs(&Names::from(vec!["x", "w"]), &[None::<RustOwnerValue<Vec<f32>>>, None], &output_names, &mut outputs, None);
+	assert!(matches!(result, Err(ort::Error::RequiredInputOmitted(name)) if name == "x"));
+
+	Ok(())
+}
@@ -0,0 +1,28 @@
+mod fixtures;
+
+use ndarray::arr1;
+use ort::{RustOwnerValue, TensorElementType, Value};
+use test_log::test;
+
+use self::fixtures::{test_session, Dim};
+
+#[test]
+fn bound_output_buffer_is_reused_across_runs() -> ort::Result<()> {
+	let session = test_session(&fixtures::identity(TensorElementType::Float32, &[Dim::Fixed(3)]))?;
+	let mut output = RustOwnerValue::<Vec<f32>>::zeros(&[3]).unwrap();
+	let output_ptr = output.as_ptr();
+
+	let mut binding = session.create_binding()?;
+	binding.bind_output_buffer("y", &mut output)?;
+	for step in 0..3 {
+		let step = step as f32;
+		binding.bind_input("x", Value::try_from(arr1(&[step, step + 1.0, step + 2.0]))?)?;
+		let outputs = binding.run()?;
+		let (shape, data) = outputs["y"].extract_raw_tensor::<f32>()?;
+		assert_eq!(shape, [3]);
+		assert_eq!(data, [step, step + 1.0, step + 2.0]);
+		// ONNX Runtime wrote into the bound buffer rather than allocating a new one
+		assert_eq!(data.as_ptr(), output_ptr);
+	}
+	Ok(())
+}
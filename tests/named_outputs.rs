@@ -0,0 +1,53 @@
+mod fixtures;
+
+use ort::{Names, RustOwnerValue, Values};
+use test_log::test;
+
+use self::fixtures::test_session;
+
+#[test]
+fn run_with_values_named() -> ort::Result<()> {
+	let session = test_session(&fixtures::add())?;
+
+	let input_names = Names::from(vec!["a", "b"]);
+	let inputs = Values::new(vec![
+		RustOwnerValue::new(&[2], vec![1.0f32, 2.0]).expect("Could not create input"),
+		RustOwnerValue::new(&[2], vec![0.5f32; 2]).expect("Could not create input")
+	]);
+	let output_names = Names::single("c");
+	let mut outputs = Values::new(vec![RustOwnerValue::<Vec<f32>>::zeros(&[2]).expect("Could not create output")]);
+
+	let named = session.run_with_values_named(&input_names, &inputs, &output_names, &mut outputs, None)?;
+	assert_eq!(named.len(), 1);
+	assert_eq!(named[0].0, "c");
+	assert_eq!(named[0].1.as_slice(), [1.5, 2.5]);
+
+	Ok(())
+}
+
+#[test]
+fn run_with_named_outputs() -> ort::Result<()> {
+	let session = test_session(&fixtures::identity_and_neg())?;
+
+	let input_names = Names::single("x");
+	let inputs = [RustOwnerValue::new(&[3], vec![1.0f32, -2.0, 3.0]).expect("Could not create input")];
+	// only the second output is pre-allocated; the first is allocated by ONNX Runtime
+	let mut z = RustOwnerValue::<Vec<f32>>::zeros(&[3]).expect("Could not create output");
+
+	let allocated = session.run_with_named_outputs(&input_names, &inputs, &mut [("z", &mut z)], None)?;
+	assert_eq!(allocated.len(), 1);
+	assert_eq!(allocated["y"].extract_raw_tensor::<f32>()?.1, [1.0, -2.0, 3.0]);
+	assert_eq!(z.as_slice(), [-1.0, 2.0, -3.0]);
+
+	match session.run_with_named_outputs(&input_names, &inputs, &mut [("w", &mut z)], None) {
+		Err(ort::Error::UnknownOutput(name)) => assert_eq!(name, "w"),
+		other => panic!("expected UnknownOutput, got {:?}", other.map(|outputs| outputs.len()))
+	}
+
+	let mut other_z = RustOwnerValue::<Vec<f32>>::zeros(&[3]).expect("Could not create output");
+	match session.run_with_named_outputs(&input_names, &inputs, &mut [("z", &mut z), ("z", &mut other_z)], None) {
+		Err(ort::Error::DuplicateOutput(name)) => assert_eq!(name, "z"),
+		other => panic!("expected DuplicateOutput, got {:?}", other.map(|outputs| outputs.len()))
+	}
+	Ok(())
+}
@@ -0,0 +1,48 @@
+mod fixtures;
+
+use ort::{Names, RustOwnerValue, Session, TensorElementType};
+use test_log::test;
+
+/// `identity_and_neg` with output type validation enabled.
+fn validating_session() -> ort::Result<Session> {
+	ort::init().with_name("integration_test").commit()?;
+	Session::builder()?
+		.with_output_type_validation(true)?
+		.with_model_from_memory(&fixtures::identity_and_neg())
+}
+
+#[test]
+fn partial_outputs_type_mismatch() -> ort::Result<()> {
+	let session = validating_session()?;
+
+	let input_names = Names::single("x");
+	let inputs = [RustOwnerValue::new(&[3], vec![1.0f32, -2.0, 3.0]).expect("Could not create input")];
+	let output_names = Names::from(vec!["y", "z"]);
+	// `z` is a `Float32` tensor, but its container holds bytes; `y` is allocated by ONNX Runtime & must be released
+	let mut bytes = vec![0u8; 3 * std::mem::size_of::<f32>()];
+	let z = RustOwnerValue::with_any_type_mut(&[3], &mut bytes, TensorElementType::Float32).expect("Could not create output");
+	let mut outputs = [None, Some(z)];
+	let result = session.run_with_partial_outputs(&input_names, &inputs, &output_names, &mut outputs, None);
+	assert!(matches!(
+		result,
+		Err(ort::Error::OutputTypeMismatch { expected: TensorElementType::Uint8, got: TensorElementType::Float32, .. })
+	));
+	Ok(())
+}
+
+#[test]
+fn named_outputs_type_mismatch() -> ort::Result<()> {
+	let session = validating_session()?;
+
+	let input_names = Names::single("x");
+	let inputs = [RustOwnerValue::new(&[3], vec![1.0f32, -2.0, 3.0]).expect("Could not create input")];
+	// as above, `y` is allocated by ONNX Runtime & must be released when `z` fails validation
+	let mut bytes = vec![0u8; 3 * std::mem::size_of::<f32>()];
+	let mut z = RustOwnerValue::with_any_type_mut(&[3], &mut bytes, TensorElementType::Float32).expect("Could not create output");
+	let result = session.run_with_named_outputs(&input_names, &inputs, &mut [("z", &mut z)], None);
+	assert!(matches!(
+		result,
+		Err(ort::Error::OutputTypeMismatch { expected: TensorElementType::Uint8, got: TensorElementType::Float32, .. })
+	));
+	Ok(())
+}
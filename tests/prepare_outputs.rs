@@ -0,0 +1,51 @@
+mod fixtures;
+
+use ort::{Error, Names, RunError, RustOwnerValue, TensorElementType};
+use test_log::test;
+
+use self::fixtures::{test_session, Dim};
+
+#[test]
+fn prepare_outputs_from_symbolic_dimensions() -> ort::Result<()> {
+	let session = test_session(&fixtures::add())?;
+
+	// `c` is declared as `['n']`, like `a` and `b`.
+	let mut outputs = session.prepare_outputs(&[&[4], &[4]]).expect("Could not prepare outputs");
+	assert_eq!(outputs.len(), 1);
+	assert_eq!(outputs[0].type_and_shape()?.dimensions, [4]);
+
+	let inputs = [
+		RustOwnerValue::new(&[4], vec![1.0f32, 2.0, 3.0, 4.0]).expect("Could not create input"),
+		RustOwnerValue::new(&[4], vec![1.0f32; 4]).expect("Could not create input")
+	];
+	session.run_with_io_ref(&Names::from(vec!["a", "b"]), &inputs, &Names::single("c"), &mut outputs, None)?;
+	assert_eq!(outputs[0].as_slice(), [2.0, 3.0, 4.0, 5.0]);
+
+	assert!(matches!(
+		session.prepare_outputs(&[&[4]]),
+		Err(RunError::OrtError(Error::InputCountMismatch { names: 2, values: 1 }))
+	));
+	assert!(matches!(
+		session.prepare_outputs(&[&[4], &[4, 1]]),
+		Err(RunError::OrtError(Error::InputShapeMismatch { .. }))
+	));
+	Ok(())
+}
+
+#[test]
+fn prepare_outputs_fixed_shape() -> ort::Result<()> {
+	let session = test_session(&fixtures::constant(&[1.0, 2.0, 3.0]))?;
+	let outputs = session.prepare_outputs(&[]).expect("Could not prepare outputs");
+	assert_eq!(outputs[0].type_and_shape()?.dimensions, [3]);
+	Ok(())
+}
+
+#[test]
+fn prepare_outputs_requires_f32() -> ort::Result<()> {
+	let session = test_session(&fixtures::identity(TensorElementType::Int64, &[Dim::Fixed(2)]))?;
+	assert!(matches!(
+		session.prepare_outputs(&[&[2]]),
+		Err(RunError::OrtError(Error::OutputTypeMismatch { got: TensorElementType::Int64, .. }))
+	));
+	Ok(())
+}
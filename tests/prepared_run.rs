@@ -0,0 +1,32 @@
+mod fixtures;
+
+use ort::{Error, Names, RustOwnerValue};
+use test_log::test;
+
+use self::fixtures::test_session;
+
+#[test]
+fn prepared_run_rebind_input() -> ort::Result<()> {
+	let session = test_session(&fixtures::add())?;
+	let mut run = session.prepare_run(Names::from(vec!["a", "b"]), Names::single("c"));
+	let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[3]).expect("Could not create output")];
+
+	// Not every input is bound yet.
+	run.rebind_input(0, RustOwnerValue::new(&[3], vec![1.0f32, 2.0, 3.0]).expect("Could not create input"))?;
+	assert!(matches!(run.run_bound(&mut outputs, None), Err(Error::InputCountMismatch { names: 2, values: 1 })));
+
+	run.rebind_input(1, RustOwnerValue::new(&[3], vec![1.0f32; 3]).expect("Could not create input"))?;
+	run.run_bound(&mut outputs, None)?;
+	assert_eq!(outputs[0].as_slice(), [2.0, 3.0, 4.0]);
+
+	// Only `a` changes; `b` stays bound.
+	run.rebind_input(0, RustOwnerValue::new(&[3], vec![10.0f32, 20.0, 30.0]).expect("Could not create input"))?;
+	run.run_bound(&mut outputs, None)?;
+	assert_eq!(outputs[0].as_slice(), [11.0, 21.0, 31.0]);
+
+	// There is no third input name.
+	let extra = RustOwnerValue::new(&[3], vec![0.0f32; 3]).expect("Could not create input");
+	assert!(matches!(run.rebind_input(2, extra), Err(Error::InputIndexOutOfRange { index: 2, count: 2 })));
+
+	Ok(())
+}
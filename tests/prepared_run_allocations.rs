@@ -0,0 +1,78 @@
+//! Repeated runs of a [`PreparedRun`](ort::PreparedRun) must not allocate. The allocations of
+//! [`Session::run_with_io_ref`](ort::Session::run_with_io_ref), which currently collects the value pointers into new
+//! `Vec`s on every call, are reported alongside for comparison. Like `run_error_leak.rs`, this test lives in its own binary so the counting allocator only sees
+//! allocations made by this test; ONNX Runtime's own C++ allocations don't go through the Rust global allocator.
+
+mod fixtures;
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	sync::atomic::{AtomicUsize, Ordering}
+};
+
+use ort::{Names, RustOwnerValue};
+
+use self::fixtures::test_session;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const RUNS: usize = 64;
+
+/// Returns the number of allocations made by `f`.
+fn count_allocations(f: impl FnOnce() -> ort::Result<()>) -> ort::Result<usize> {
+	let before = ALLOCATIONS.load(Ordering::SeqCst);
+	f()?;
+	Ok(ALLOCATIONS.load(Ordering::SeqCst) - before)
+}
+
+#[test]
+fn prepared_run_does_not_allocate() -> ort::Result<()> {
+	let session = test_session(&fixtures::add())?;
+	let inputs = [
+		RustOwnerValue::new(&[3], vec![1.0f32, 2.0, 3.0]).expect("Could not create input"),
+		RustOwnerValue::new(&[3], vec![1.0f32; 3]).expect("Could not create input")
+	];
+	let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[3]).expect("Could not create output")];
+	let input_names = Names::from(vec!["a", "b"]);
+	let output_names = Names::single("c");
+	let mut prepared = session.prepare_run(Names::from(vec!["a", "b"]), Names::single("c"));
+
+	// Warm up any lazily-initialized state, and size the prepared run's buffers, before measuring.
+	prepared.run(&inputs, &mut outputs, None)?;
+	session.run_with_io_ref(&input_names, &inputs, &output_names, &mut outputs, None)?;
+
+	let prepared_allocations = count_allocations(|| {
+		for _ in 0..RUNS {
+			prepared.run(&inputs, &mut outputs, None)?;
+		}
+		Ok(())
+	})?;
+	let io_ref_allocations = count_allocations(|| {
+		for _ in 0..RUNS {
+			session.run_with_io_ref(&input_names, &inputs, &output_names, &mut outputs, None)?;
+		}
+		Ok(())
+	})?;
+	assert_eq!(outputs[0].as_slice(), [2.0, 3.0, 4.0]);
+
+	// only reported for comparison; `run_with_io_ref` is free to stop allocating
+	println!("{RUNS} runs: {prepared_allocations} allocations with `PreparedRun::run`, {io_ref_allocations} with `run_with_io_ref`");
+	assert_eq!(prepared_allocations, 0, "{RUNS} prepared runs made {prepared_allocations} allocations");
+	Ok(())
+}
@@ -0,0 +1,36 @@
+mod fixtures;
+
+use ort::{ModelTokenizer, Preprocessor};
+use test_log::test;
+
+use self::fixtures::test_session;
+
+#[test]
+fn model_tokenizer() -> ort::Result<()> {
+	let session = test_session(&fixtures::label_encoder(&["hello", "world"], &[7, 11]))?;
+	let tokenizer = ModelTokenizer::new(&session);
+
+	let tokens = tokenizer.process(b"world").expect("Could not tokenize");
+	assert_eq!(tokens.type_and_shape()?.dimensions, [1]);
+	assert_eq!(tokens.as_slice(), [11.0]);
+	assert_eq!(tokenizer.process(b"unknown").expect("Could not tokenize").as_slice(), [-1.0]);
+	assert!(matches!(tokenizer.process(&[0xff, 0xfe]), Err(ort::RunError::Msg(_))));
+	Ok(())
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn image_normalizer() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+	// a 2x1 image with a white and a black pixel
+	let raw = [255, 255, 255, 0, 0, 0];
+	let normalizer = ort::ImageNormalizer::new(2, 1, [0.5; 3], [0.5; 3]);
+	let tensor = normalizer.process(&raw).expect("Could not normalize");
+	assert_eq!(tensor.type_and_shape()?.dimensions, [1, 3, 1, 2]);
+	assert_eq!(tensor.as_slice(), [1.0, -1.0, 1.0, -1.0, 1.0, -1.0]);
+	assert!(matches!(normalizer.process(&raw[..3]), Err(ort::RunError::Msg(_))));
+
+	let resized = normalizer.resize_to(4, 2).process(&raw).expect("Could not normalize");
+	assert_eq!(resized.type_and_shape()?.dimensions, [1, 3, 2, 4]);
+	Ok(())
+}
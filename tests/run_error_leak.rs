@@ -0,0 +1,56 @@
+//! Failed runs must not leak. This test lives in its own binary so the counting allocator only sees allocations made
+//! by this test; ONNX Runtime's own C++ allocations don't go through the Rust global allocator, so any net growth
+//! comes from our side of the FFI boundary.
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	path::Path,
+	sync::atomic::{AtomicIsize, Ordering}
+};
+
+use ndarray::{Array, CowArray};
+use ort::{inputs, Session};
+
+struct CountingAllocator;
+
+static LIVE_BYTES: AtomicIsize = AtomicIsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		LIVE_BYTES.fetch_add(layout.size() as isize, Ordering::SeqCst);
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		LIVE_BYTES.fetch_sub(layout.size() as isize, Ordering::SeqCst);
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn failed_run_does_not_leak_names() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+
+	// The model expects 3 channels in the last dimension; give it 4 so that `Run` fails.
+	let array = CowArray::from(Array::<f32, _>::zeros((1, 4, 4, 4)));
+
+	// Warm up any lazily-initialized state before measuring.
+	assert!(session.run(inputs![&array]?).is_err());
+
+	let before = LIVE_BYTES.load(Ordering::SeqCst);
+	for _ in 0..64 {
+		assert!(session.run(inputs![&array]?).is_err());
+	}
+	let after = LIVE_BYTES.load(Ordering::SeqCst);
+	assert_eq!(after - before, 0, "failed runs leaked {} bytes", after - before);
+
+	Ok(())
+}
@@ -0,0 +1,35 @@
+mod fixtures;
+
+use ndarray::{arr1, CowArray};
+use ort::inputs;
+use test_log::test;
+
+use self::fixtures::test_session;
+
+#[test]
+fn run_subset_of_outputs() -> ort::Result<()> {
+	let session = test_session(&fixtures::identity_and_neg())?;
+	let x = CowArray::from(arr1(&[1.0f32, -2.0, 3.0]).into_dyn());
+
+	let outputs = session.run_subset(inputs![&x]?, &["z"])?;
+	assert_eq!(outputs.len(), 1);
+	assert!(!outputs.contains_key("y"));
+	assert_eq!(outputs["z"].extract_raw_tensor::<f32>()?.1, [-1.0, 2.0, -3.0]);
+	assert_eq!(outputs[0].extract_raw_tensor::<f32>()?.1, [-1.0, 2.0, -3.0]);
+
+	// outputs are returned in the requested order, not the model's
+	let outputs = session.run_subset(inputs![&x]?, &["z", "y"])?;
+	assert_eq!(outputs[1].extract_raw_tensor::<f32>()?.1, [1.0, -2.0, 3.0]);
+	Ok(())
+}
+
+#[test]
+fn run_subset_unknown_output() -> ort::Result<()> {
+	let session = test_session(&fixtures::identity_and_neg())?;
+	let x = CowArray::from(arr1(&[1.0f32]).into_dyn());
+	match session.run_subset(inputs![&x]?, &["y", "w"]) {
+		Err(ort::Error::UnknownOutput(name)) => assert_eq!(name, "w"),
+		other => panic!("expected UnknownOutput, got {:?}", other.map(|outputs| outputs.len()))
+	}
+	Ok(())
+}
@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use ort::{Names, OnnxType, RustOwnerValue, Session, ValueType};
+use test_log::test;
+
+/// sequence.onnx outputs the sequence `[[1, 2, 3], [4, 5]]`; it is equivalent to the model produced by:
+///
+/// ```python
+/// import onnx
+/// from onnx import helper, TensorProto
+///
+/// a = helper.make_node('Constant', [], ['a'], name='a', value=helper.make_tensor('value', TensorProto.FLOAT, [3], [1.0, 2.0, 3.0]))
+/// b = helper.make_node('Constant', [], ['b'], name='b', value=helper.make_tensor('value', TensorProto.FLOAT, [2], [4.0, 5.0]))
+/// s = helper.make_node('SequenceConstruct', ['a', 'b'], ['s'], name='sequence')
+/// output = helper.make_tensor_sequence_value_info('s', TensorProto.FLOAT, None)
+/// graph = helper.make_graph([a, b, s], 'sequence', [], [output])
+/// onnx.save(helper.make_model(graph, opset_imports=[helper.make_opsetid('', 13)], ir_version=8), 'sequence.onnx')
+/// ```
+#[test]
+fn sequence_as_owned_values() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("sequence.onnx"))
+		.expect("Could not load model");
+	assert!(matches!(session.outputs[0].output_type, ValueType::Sequence(_)));
+
+	let input_names = Names::from(Vec::<&str>::new());
+	let inputs: [RustOwnerValue<Vec<f32>>; 0] = [];
+	let output_names = Names::single("s");
+	let mut outputs: [Option<RustOwnerValue<Vec<f32>>>; 1] = [None];
+	let mut outputs = session.run_with_partial_outputs(&input_names, &inputs, &output_names, &mut outputs, None)?;
+	let (_, sequence) = outputs.remove(0);
+	assert_eq!(sequence.value_type()?, OnnxType::Sequence);
+
+	let elements = sequence.as_sequence(&session)?;
+	// the elements are owned, so they outlive the sequence
+	drop(sequence);
+	assert_eq!(elements.len(), 2);
+	assert!(elements.iter().all(|element| element.value_type().ok() == Some(OnnxType::Tensor)));
+	assert_eq!(elements[0].extract_raw_tensor::<f32>()?, (vec![3], &[1.0, 2.0, 3.0][..]));
+	assert_eq!(elements[1].extract_raw_tensor::<f32>()?, (vec![2], &[4.0, 5.0][..]));
+
+	Ok(())
+}
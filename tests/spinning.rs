@@ -0,0 +1,19 @@
+mod fixtures;
+
+use ndarray::{arr1, CowArray};
+use ort::inputs;
+use test_log::test;
+
+#[test]
+fn spinning_disabled() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+	let session = ort::Session::builder()?
+		.with_intra_op_spinning(false)?
+		.with_inter_op_spinning(false)?
+		.with_parallel_execution(true)?
+		.with_model_from_memory(&fixtures::add())?;
+	let a = CowArray::from(arr1(&[1.0f32, 2.0]).into_dyn());
+	let outputs = session.run(inputs!["a" => &a, "b" => &a]?)?;
+	assert_eq!(outputs["c"].extract_raw_tensor::<f32>()?.1, [2.0, 4.0]);
+	Ok(())
+}
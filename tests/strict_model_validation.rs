@@ -0,0 +1,22 @@
+mod fixtures;
+
+use test_log::test;
+
+#[test]
+fn strict_model_validation() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+	let model = fixtures::identity_with_unused_initializer();
+	assert!(ort::Session::builder()?.strict_model_validation(false)?.with_model_from_memory(&model).is_ok());
+	match ort::Session::builder()?.strict_model_validation(true)?.with_model_from_memory(&model) {
+		Err(ort::Error::ModelValidation(warnings)) => assert!(
+			warnings
+				.iter()
+				.any(|warning| warning.contains("Removing initializer 'w'") && warning.contains("not used by any node")),
+			"unexpected warnings: {warnings:?}"
+		),
+		other => panic!("expected a model validation error, got {other:?}")
+	}
+	// a well-formed model loads without warnings
+	ort::Session::builder()?.strict_model_validation(true)?.with_model_from_memory(&fixtures::add())?;
+	Ok(())
+}
@@ -1,10 +1,17 @@
-use std::path::Path;
+mod fixtures;
+
+use std::{
+	path::Path,
+	sync::{Arc, Barrier}
+};
 
 use image::RgbImage;
 use ndarray::{Array, CowArray, Ix4};
-use ort::{inputs, GraphOptimizationLevel, Session, Tensor};
+use ort::{inputs, GraphOptimizationLevel, Names, RunError, RunOptions, RustOwnerValue, Session, SessionBuilder, Tensor, TensorElementType};
 use test_log::test;
 
+use self::fixtures::assert_values_close;
+
 fn load_input_image<P: AsRef<Path>>(name: P) -> RgbImage {
 	// Load image, converting to RGB format
 	image::open(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join(name))
@@ -19,6 +26,16 @@ fn convert_image_to_cow_array(img: &RgbImage) -> CowArray<'_, f32, Ix4> {
 	CowArray::from(array)
 }
 
+/// Loads upsample.onnx with basic optimizations on a single thread, after applying `builder` to the session builder
+/// for any test-specific options.
+fn upsample_session(builder: impl FnOnce(SessionBuilder) -> ort::Result<SessionBuilder>) -> ort::Result<Session> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session_data =
+		std::fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx")).expect("Could not open model from file");
+	builder(Session::builder()?.with_optimization_level(GraphOptimizationLevel::Level1)?.with_intra_threads(1)?)?.with_model_from_memory(&session_data)
+}
+
 /// This test verifies that dynamically sized inputs and outputs work. It loads and runs
 /// upsample.onnx, which was produced via:
 ///
@@ -44,15 +61,7 @@ fn convert_image_to_cow_array(img: &RgbImage) -> CowArray<'_, f32, Ix4> {
 fn upsample() -> ort::Result<()> {
 	const IMAGE_TO_LOAD: &str = "mushroom.png";
 
-	ort::init().with_name("integration_test").commit()?;
-
-	let session_data =
-		std::fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx")).expect("Could not open model from file");
-	let session = Session::builder()?
-		.with_optimization_level(GraphOptimizationLevel::Level1)?
-		.with_intra_threads(1)?
-		.with_model_from_memory(&session_data)
-		.expect("Could not read model from memory");
+	let session = upsample_session(Ok)?;
 
 	let metadata = session.metadata()?;
 	assert_eq!(metadata.name()?, "tf2onnx");
@@ -113,3 +122,190 @@ fn upsample_with_ort_model() -> ort::Result<()> {
 
 	Ok(())
 }
+
+/// Errors returned by ONNX Runtime during a run should carry its original message, so that shape mismatches and the like
+/// can be diagnosed from the error alone.
+#[test]
+fn upsample_invalid_dimensions() -> ort::Result<()> {
+	let session = upsample_session(|builder| builder.with_log_id("upsample"))?;
+
+	// The model expects 3 channels in the last dimension; give it 4.
+	let array = CowArray::from(Array::<f32, _>::zeros((1, 4, 4, 4)));
+
+	let message = match session.run(inputs![&array]?) {
+		Ok(_) => panic!("run with invalid dimensions should fail"),
+		Err(e) => e.to_string()
+	};
+	assert!(message.contains("Got invalid dimensions"), "ONNX Runtime's message was lost: {message}");
+	assert!(message.contains("[upsample]"), "session log ID missing from error: {message}");
+
+	Ok(())
+}
+
+/// A terminated [`RunOptions`] can be reused once the terminate flag is cleared.
+#[test]
+fn upsample_terminate_and_reuse_run_options() -> ort::Result<()> {
+	let session = upsample_session(Ok)?;
+
+	let array = CowArray::from(Array::<f32, _>::zeros((1, 4, 4, 3)));
+	let run_options = Arc::new(RunOptions::new()?);
+
+	run_options.set_terminate()?;
+	assert!(session.run_with_options(inputs![&array]?, Arc::clone(&run_options)).is_err());
+
+	run_options.set_unterminate()?;
+	let outputs = session.run_with_options(inputs![&array]?, Arc::clone(&run_options))?;
+	let output: Tensor<f32> = outputs[0].extract_tensor()?;
+	assert_eq!(output.view().shape(), [1, 8, 8, 3]);
+
+	Ok(())
+}
+
+#[test]
+fn upsample_terminate_from_another_thread() -> ort::Result<()> {
+	let session = upsample_session(Ok)?;
+
+	// large enough that each run takes a while, so the flag is likely set in the middle of one
+	let array = CowArray::from(Array::<f32, _>::zeros((1, 256, 256, 3)));
+	let run_options = Arc::new(RunOptions::new()?);
+	session.run_with_options(inputs![&array]?, Arc::clone(&run_options))?;
+
+	// the watchdog terminates once the main thread is running in a loop
+	let started = Arc::new(Barrier::new(2));
+	let watchdog = {
+		let run_options = Arc::clone(&run_options);
+		let started = Arc::clone(&started);
+		std::thread::spawn(move || {
+			started.wait();
+			run_options.set_terminate()
+		})
+	};
+	started.wait();
+	let mut result = Ok(());
+	for _ in 0..10_000 {
+		result = session.run_with_options(inputs![&array]?, Arc::clone(&run_options)).map(drop);
+		if result.is_err() {
+			break;
+		}
+	}
+	watchdog.join().expect("watchdog thread panicked")?;
+	assert!(matches!(result, Err(ort::Error::SessionRun(_))), "runs weren't terminated: {result:?}");
+
+	Ok(())
+}
+
+#[test]
+fn upsample_input_builder() -> ort::Result<()> {
+	let session = upsample_session(Ok)?;
+	let input_name = session.inputs[0].name.clone();
+
+	let input = session.input_builder(&input_name)?.data(vec![0.0f32; 4 * 4 * 3]).shape([1, 4, 4, 3]).build();
+	assert!(input.is_ok());
+
+	let wrong_channels = session.input_builder(&input_name)?.data(vec![0.0f32; 4 * 4 * 4]).shape([1, 4, 4, 4]).build();
+	assert!(matches!(wrong_channels, Err(RunError::OrtError(ort::Error::InputShapeMismatch { .. }))));
+
+	let wrong_type = session.input_builder(&input_name)?.data(vec![0i64; 4 * 4 * 3]).shape([1, 4, 4, 3]).build();
+	assert!(matches!(wrong_type, Err(RunError::OrtError(ort::Error::NonMatchingDataTypes { .. }))));
+
+	let no_shape = session.input_builder(&input_name)?.data(vec![0.0f32; 4 * 4 * 3]).build();
+	assert!(matches!(no_shape, Err(RunError::OrtError(ort::Error::MissingInputShape(_)))));
+
+	assert!(matches!(session.input_builder("not_an_input"), Err(ort::Error::UnknownInput(_))));
+
+	Ok(())
+}
+
+#[test]
+fn upsample_input_type_validation() -> ort::Result<()> {
+	let session = upsample_session(|builder| builder.with_input_type_validation(true))?;
+
+	let input_names = Names::from([session.inputs[0].name.as_str()]);
+	let output_names = Names::from([session.outputs[0].name.as_str()]);
+	let inputs = [RustOwnerValue::<Vec<f64>>::zeros(&[1, 4, 4, 3]).expect("Could not create input")];
+	let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[1, 8, 8, 3]).expect("Could not create output")];
+
+	let result = session.run_with_io_ref(&input_names, &inputs, &output_names, &mut outputs, None);
+	assert!(matches!(
+		result,
+		Err(ort::Error::InputTypeMismatch { expected: TensorElementType::Float32, got: TensorElementType::Float64, .. })
+	));
+
+	Ok(())
+}
+
+#[test]
+fn upsample_output_type_validation() -> ort::Result<()> {
+	let session = upsample_session(|builder| builder.with_output_type_validation(true))?;
+
+	let input_names = Names::single(&session.inputs[0].name);
+	let output_names = Names::single(&session.outputs[0].name);
+	let inputs = [RustOwnerValue::<Vec<f32>>::zeros(&[1, 4, 4, 3]).expect("Could not create input")];
+
+	let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[1, 8, 8, 3]).expect("Could not create output")];
+	session.run_with_io_ref(&input_names, &inputs, &output_names, &mut outputs, None)?;
+
+	// the output buffer is a `Float32` tensor, but its container holds bytes
+	let mut bytes = vec![0u8; 8 * 8 * 3 * std::mem::size_of::<f32>()];
+	let mut outputs = [RustOwnerValue::with_any_type_mut(&[1, 8, 8, 3], &mut bytes, TensorElementType::Float32)
+		.expect("Could not create output")];
+	let result = session.run_with_io_ref(&input_names, &inputs, &output_names, &mut outputs, None);
+	assert!(matches!(
+		result,
+		Err(ort::Error::OutputTypeMismatch { expected: TensorElementType::Uint8, got: TensorElementType::Float32, .. })
+	));
+
+	Ok(())
+}
+
+#[test]
+fn upsample_chained() -> ort::Result<()> {
+	let session = upsample_session(Ok)?;
+
+	let input_names = Names::single(&session.inputs[0].name);
+	let output_names = Names::single(&session.outputs[0].name);
+	let inputs = [RustOwnerValue::<Vec<f32>>::full(&[1, 4, 4, 3], 0.5).expect("Could not create input")];
+
+	// let ONNX Runtime allocate the intermediate output, then feed it straight into the next run
+	let mut intermediate: [Option<RustOwnerValue<Vec<f32>>>; 1] = [None];
+	let intermediate = session.run_with_partial_outputs(&input_names, &inputs, &output_names, &mut intermediate, None)?;
+	let (_, intermediate) = &intermediate[0];
+	assert_eq!(intermediate.extract_raw_tensor::<f32>()?.0, [1, 8, 8, 3]);
+
+	let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[1, 16, 16, 3]).expect("Could not create output")];
+	session.run_with_value_inputs(&input_names, &[intermediate], &output_names, &mut outputs, None)?;
+	let expected = RustOwnerValue::<Vec<f32>>::full(&[1, 16, 16, 3], 0.5).expect("Could not create expected output");
+	assert_values_close(&outputs[0], &expected, 0.0, 1e-6);
+
+	Ok(())
+}
+
+#[test]
+fn upsample_concurrent_runs() -> ort::Result<()> {
+	let session = Arc::new(upsample_session(Ok)?);
+
+	let threads: Vec<_> = (0..8)
+		.map(|i| {
+			let session = Arc::clone(&session);
+			std::thread::spawn(move || -> ort::Result<()> {
+				let input_names = Names::single(&session.inputs[0].name);
+				let output_names = Names::single(&session.outputs[0].name);
+				// each thread upsamples a different constant, so mixed up outputs would be noticed
+				let value = i as f32 / 8.0;
+				let inputs = [RustOwnerValue::<Vec<f32>>::full(&[1, 4, 4, 3], value).expect("Could not create input")];
+				let mut outputs = [RustOwnerValue::<Vec<f32>>::zeros(&[1, 8, 8, 3]).expect("Could not create output")];
+				let expected = RustOwnerValue::<Vec<f32>>::full(&[1, 8, 8, 3], value).expect("Could not create expected output");
+				for _ in 0..16 {
+					session.run_with_io_ref(&input_names, &inputs, &output_names, &mut outputs, None)?;
+					assert_values_close(&outputs[0], &expected, 0.0, 1e-6);
+				}
+				Ok(())
+			})
+		})
+		.collect();
+	for thread in threads {
+		thread.join().expect("run thread panicked")?;
+	}
+
+	Ok(())
+}
@@ -18,6 +18,8 @@ fn vectorizer() -> ort::Result<()> {
 	assert_eq!(metadata.producer()?, "skl2onnx");
 	assert_eq!(metadata.description()?, "test description");
 	assert_eq!(metadata.custom("custom_key")?.as_deref(), Some("custom_value"));
+	assert!(metadata.custom_keys()?.contains(&"custom_key".to_owned()));
+	assert_eq!(metadata.custom_map()?.get("custom_key").map(String::as_str), Some("custom_value"));
 
 	let array = ndarray::CowArray::from(ndarray::Array::from_shape_vec((1,), vec!["document".to_owned()]).unwrap());
 